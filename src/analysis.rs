@@ -0,0 +1,259 @@
+use crate::exercises::custom::SKIPPED_DIRS;
+use crate::layout::{Finger, KeyboardLayout};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const FINGERS: [Finger; 10] = [
+    Finger::LeftPinky,
+    Finger::LeftRing,
+    Finger::LeftMiddle,
+    Finger::LeftIndex,
+    Finger::LeftThumb,
+    Finger::RightThumb,
+    Finger::RightIndex,
+    Finger::RightMiddle,
+    Finger::RightRing,
+    Finger::RightPinky,
+];
+
+// Where a character is typed: row, position in the row and finger
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct KeyPosition {
+    row: usize,
+    index: usize,
+    finger: Finger,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LayoutAnalysis {
+    pub layout: String,
+    pub keystrokes: usize,
+    // Characters other than whitespace the layout has no key for
+    pub unmapped: usize,
+    // Layouts have no Space key, so spaces are counted apart from the fingers
+    pub spaces: usize,
+    pub finger_counts: HashMap<Finger, usize>,
+    pub home_row_keystrokes: usize,
+    pub bigrams: usize,
+    pub alternations: usize,
+    pub same_finger_bigrams: usize,
+    pub row_jumps: usize,
+}
+
+fn percent(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 * 100.0 / total as f64
+    }
+}
+
+impl LayoutAnalysis {
+    pub fn finger_load(&self, finger: Finger) -> f64 {
+        percent(
+            self.finger_counts.get(&finger).copied().unwrap_or(0),
+            self.keystrokes,
+        )
+    }
+
+    pub fn alternation_rate(&self) -> f64 {
+        percent(self.alternations, self.bigrams)
+    }
+
+    pub fn same_finger_rate(&self) -> f64 {
+        percent(self.same_finger_bigrams, self.bigrams)
+    }
+
+    pub fn row_jump_rate(&self) -> f64 {
+        percent(self.row_jumps, self.bigrams)
+    }
+
+    pub fn home_row_rate(&self) -> f64 {
+        percent(self.home_row_keystrokes, self.keystrokes)
+    }
+}
+
+fn key_positions(layout: &KeyboardLayout) -> HashMap<char, KeyPosition> {
+    let mut positions = HashMap::new();
+    for (row, layout_row) in layout.rows.iter().enumerate() {
+        for (index, key) in layout_row.keys.iter().enumerate() {
+            let Some(finger) = key.finger else {
+                continue;
+            };
+            if key.named {
+                continue;
+            }
+            for c in key.chars() {
+                positions
+                    .entry(c)
+                    .or_insert(KeyPosition { row, index, finger });
+            }
+        }
+    }
+    positions
+}
+
+// Counts every keystroke of the corpus on the layout. Bigrams are pairs of
+// consecutive keys, so whitespace and unmapped characters break them
+pub fn analyze(layout: &KeyboardLayout, texts: &[String]) -> LayoutAnalysis {
+    let positions = key_positions(layout);
+    let mut analysis = LayoutAnalysis {
+        layout: layout.name.clone(),
+        ..Default::default()
+    };
+
+    for text in texts {
+        let mut previous: Option<KeyPosition> = None;
+        for c in text.chars() {
+            let Some(&key) = positions.get(&c) else {
+                if c == ' ' {
+                    analysis.spaces += 1;
+                } else if !c.is_whitespace() {
+                    analysis.unmapped += 1;
+                }
+                previous = None;
+                continue;
+            };
+
+            analysis.keystrokes += 1;
+            *analysis.finger_counts.entry(key.finger).or_insert(0) += 1;
+            if key.row == layout.home_row {
+                analysis.home_row_keystrokes += 1;
+            }
+
+            if let Some(prev) = previous {
+                analysis.bigrams += 1;
+                if prev.finger.is_left() != key.finger.is_left() {
+                    analysis.alternations += 1;
+                } else if prev.row.abs_diff(key.row) >= 2 {
+                    // Skipping over the home row with one hand
+                    analysis.row_jumps += 1;
+                }
+                // Repeating the same key is not a same-finger bigram
+                if prev.finger == key.finger && (prev.row, prev.index) != (key.row, key.index) {
+                    analysis.same_finger_bigrams += 1;
+                }
+            }
+            previous = Some(key);
+        }
+    }
+
+    analysis
+}
+
+// Side by side table with one column per layout
+pub fn format_report(results: &[LayoutAnalysis]) -> String {
+    let label_width = 22;
+    let column_width = results
+        .iter()
+        .map(|r| r.layout.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(10)
+        + 2;
+
+    let mut lines = Vec::new();
+    let mut push_row = |label: &str, values: Vec<String>| {
+        let mut line = format!("{:<label_width$}", label);
+        for value in values {
+            line.push_str(&format!("{:>column_width$}", value));
+        }
+        lines.push(line.trim_end().to_string());
+    };
+    let pct = |value: f64| format!("{:.1}%", value);
+
+    push_row("", results.iter().map(|r| r.layout.clone()).collect());
+    push_row(
+        "Keystrokes",
+        results.iter().map(|r| r.keystrokes.to_string()).collect(),
+    );
+    push_row(
+        "Unmapped characters",
+        results.iter().map(|r| r.unmapped.to_string()).collect(),
+    );
+    push_row(
+        "Home row usage",
+        results.iter().map(|r| pct(r.home_row_rate())).collect(),
+    );
+    push_row(
+        "Hand alternation",
+        results.iter().map(|r| pct(r.alternation_rate())).collect(),
+    );
+    push_row(
+        "Same-finger bigrams",
+        results.iter().map(|r| pct(r.same_finger_rate())).collect(),
+    );
+    push_row(
+        "Row jumps",
+        results.iter().map(|r| pct(r.row_jump_rate())).collect(),
+    );
+    push_row(
+        "Spaces (thumbs)",
+        results.iter().map(|r| r.spaces.to_string()).collect(),
+    );
+    push_row("Finger load (no Space)", Vec::new());
+    for finger in FINGERS {
+        // Built-in layouts give thumbs no character keys
+        if results.iter().all(|r| r.finger_load(finger) == 0.0) {
+            continue;
+        }
+        push_row(
+            &format!("  {}", finger.display()),
+            results.iter().map(|r| pct(r.finger_load(finger))).collect(),
+        );
+    }
+
+    lines.join("\n")
+}
+
+#[derive(Debug, Default)]
+pub struct Corpus {
+    pub texts: Vec<String>,
+    // Entries left out of the corpus and why
+    pub skipped: Vec<(PathBuf, &'static str)>,
+}
+
+// Unlike the exercise loader there is no size limit, since a corpus is meant to
+// be large, and everything left out is reported instead of dropped silently
+pub fn read_corpus(paths: &[PathBuf]) -> io::Result<Corpus> {
+    let mut corpus = Corpus::default();
+    for path in paths {
+        if path.is_dir() {
+            walk_corpus_dir(path, &mut corpus)?;
+        } else {
+            corpus.texts.push(fs::read_to_string(path)?);
+        }
+    }
+    Ok(corpus)
+}
+
+fn walk_corpus_dir(path: &Path, corpus: &mut Corpus) -> io::Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(path)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.path());
+
+    for entry in entries {
+        let entry_path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let file_type = entry.file_type()?;
+        if name.starts_with('.') {
+            corpus.skipped.push((entry_path, "hidden"));
+        } else if file_type.is_dir() {
+            if SKIPPED_DIRS.contains(&name.as_str()) {
+                corpus
+                    .skipped
+                    .push((entry_path, "build or dependency output"));
+            } else {
+                walk_corpus_dir(&entry_path, corpus)?;
+            }
+        } else if file_type.is_file() {
+            match fs::read(&entry_path).map(String::from_utf8) {
+                Ok(Ok(text)) => corpus.texts.push(text),
+                Ok(Err(_)) => corpus.skipped.push((entry_path, "not UTF-8 text")),
+                Err(_) => corpus.skipped.push((entry_path, "unreadable")),
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,91 +1,198 @@
 use crate::exercises::*;
-use crate::stats::Stats;
+use crate::history::{History, SessionRecord, SessionResults};
+use crate::keymaps::{
+    self, KeyChord, Keymap, KeymapError, default_keymaps, import_nvim_config, load_keymaps,
+    parse_notation,
+};
+use crate::layout::KeyboardLayout;
+use crate::romaji::{self, RomajiStep};
+use crate::stats::{KeyStat, Stats};
+use crate::text::{grapheme_at, grapheme_count, graphemes, pop_grapheme};
 use crate::translations::Translations;
-use crate::types::{AppMode, ExerciseCount, KeyboardLayout, PressedKey};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::types::{
+    AppMode, ExerciseCount, HeatmapMode, HistorySort, MistakePolicy, ModifierState, PressedKey,
+};
+use crate::vim_buffer::VimBuffer;
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, ModifierKeyCode,
+};
 use rand::Rng;
-use std::time::Instant;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const MIN_SESSION_SECS: f32 = 1.0;
 
 pub struct App {
     pub mode: AppMode,
     pub keyboard_layout: KeyboardLayout,
-    pub current_layout_name: String,
+    pub emulation_source: Option<KeyboardLayout>,
+    pub available_layouts: Vec<KeyboardLayout>,
     pub stats: Stats,
     pub target_text: String,
     pub typed_text: String,
+    pub pending_input: String,
+    pub typed_errors: HashMap<usize, char>,
     pub current_key_index: usize,
     pub last_pressed_key: Option<PressedKey>,
     pub menu_selected: usize,
     pub submenu_selected: usize,
     pub count_selected: usize,
     pub pending_mode: Option<AppMode>,
-    pub nvim_keymaps: Vec<String>,
+    pub nvim_keymaps: Vec<Keymap>,
+    pub keymap_leader: KeyChord,
+    pub target_chords: Vec<KeyChord>,
+    pub vim_challenge: Option<VimChallenge>,
+    pub vim_buffer: Option<VimBuffer>,
+    pub vim_keystrokes: usize,
+    pub vim_last_score: Option<(usize, VimChallenge)>,
+    pub japanese_sentence: Option<JapaneseSentence>,
+    pub custom_exercises: Vec<String>,
     pub shift_pressed: bool,
     pub ctrl_pressed: bool,
     pub alt_pressed: bool,
+    // Set when the terminal reports modifier presses and releases on their own
+    pub keyboard_enhanced: bool,
+    pub held_modifiers: HashSet<ModifierKeyCode>,
+    pub caps_lock: bool,
+    pub key_modifiers: ModifierState,
     pub translations: Translations,
     pub exercise_count: ExerciseCount,
+    pub mistake_policy: MistakePolicy,
+    pub skip_indentation: bool,
     pub exercises_completed: usize,
+    pub exercises_failed: usize,
+    pub history: History,
+    pub history_sort: HistorySort,
+    pub history_mode_filter: Option<AppMode>,
+    pub history_layout_filter: Option<String>,
+    pub history_scroll: usize,
+    pub heatmap_mode: HeatmapMode,
+    // History key stats for the current layout, aggregated once instead of
+    // on every frame the heatmap is drawn
+    pub history_key_stats: Vec<KeyStat>,
+    pub results: Option<SessionResults>,
 }
 
 impl App {
-    pub fn new(lang: String, layout: String) -> Self {
-        let keyboard_layout = match layout.as_str() {
-            "qwerty" => KeyboardLayout::Qwerty,
-            _ => KeyboardLayout::Dvorak,
-        };
+    pub fn new(lang: String, keyboard_layout: KeyboardLayout) -> Self {
+        Self::with_history(lang, keyboard_layout, History::load())
+    }
 
-        let current_layout_name = match keyboard_layout {
-            KeyboardLayout::Dvorak => "Dvorak Programmer",
-            KeyboardLayout::Qwerty => "QWERTY",
+    // Tests pass an in-memory History so finished sessions never reach the
+    // user's history file
+    pub fn with_history(lang: String, keyboard_layout: KeyboardLayout, history: History) -> Self {
+        let mut available_layouts = KeyboardLayout::builtin();
+        if !available_layouts.contains(&keyboard_layout) {
+            available_layouts.push(keyboard_layout.clone());
         }
-        .to_string();
+        let history_key_stats = history.key_stats(&keyboard_layout.name);
 
         Self {
             mode: AppMode::Menu,
             keyboard_layout,
-            current_layout_name,
+            emulation_source: None,
+            available_layouts,
             stats: Stats::new(),
             target_text: String::new(),
             typed_text: String::new(),
+            pending_input: String::new(),
+            typed_errors: HashMap::new(),
             current_key_index: 0,
             last_pressed_key: None,
             menu_selected: 0,
             submenu_selected: 0,
             count_selected: 0,
             pending_mode: None,
-            nvim_keymaps: Vec::new(),
+            nvim_keymaps: default_keymaps(),
+            keymap_leader: KeyChord::char('\\'),
+            target_chords: Vec::new(),
+            vim_challenge: None,
+            vim_buffer: None,
+            vim_keystrokes: 0,
+            vim_last_score: None,
+            japanese_sentence: None,
+            custom_exercises: Vec::new(),
             shift_pressed: false,
             ctrl_pressed: false,
             alt_pressed: false,
+            keyboard_enhanced: false,
+            held_modifiers: HashSet::new(),
+            caps_lock: false,
+            key_modifiers: ModifierState::default(),
             translations: Translations::new(&lang),
             exercise_count: ExerciseCount::All,
+            mistake_policy: MistakePolicy::default(),
+            skip_indentation: false,
             exercises_completed: 0,
+            exercises_failed: 0,
+            history,
+            history_sort: HistorySort::Newest,
+            history_mode_filter: None,
+            history_layout_filter: None,
+            history_scroll: 0,
+            heatmap_mode: HeatmapMode::Off,
+            history_key_stats,
+            results: None,
         }
     }
 
-    pub fn load_nvim_keymaps(&mut self) {
-        if let Ok(content) = std::fs::read_to_string("/tmp/lazy-dvorak-keymaps.json") {
-            if let Ok(keymaps) = serde_json::from_str::<Vec<String>>(&content) {
-                self.nvim_keymaps = keymaps;
+    fn refresh_history_key_stats(&mut self) {
+        self.history_key_stats = self.history.key_stats(&self.keyboard_layout.name);
+    }
+
+    // An explicit source has to load; the default config file is optional and
+    // the sample keymaps stay in place when nothing provides any mappings.
+    // Mappings imported from a Neovim config are added after the file's ones
+    pub fn load_nvim_keymaps(
+        &mut self,
+        source: Option<&Path>,
+        nvim_config: Option<&Path>,
+    ) -> Result<(), KeymapError> {
+        let mut loaded = match source {
+            Some(path) => load_keymaps(path)?,
+            None => match keymaps::default_path() {
+                Some(path) if path.exists() => load_keymaps(&path)?,
+                _ => Vec::new(),
+            },
+        };
+
+        if let Some(dir) = nvim_config {
+            let imported = import_nvim_config(dir)
+                .map_err(|e| KeymapError::Io(dir.display().to_string(), e))?;
+            for keymap in imported {
+                if !loaded
+                    .iter()
+                    .any(|k| k.lhs == keymap.lhs && k.mode == keymap.mode)
+                {
+                    loaded.push(keymap);
+                }
             }
         }
 
-        if self.nvim_keymaps.is_empty() {
-            self.nvim_keymaps = vec![
-                "<leader>ff".to_string(),
-                "<C-n>".to_string(),
-                "<Space>b".to_string(),
-                "<C-w>v".to_string(),
-                "<A-j>".to_string(),
-            ];
+        if !loaded.is_empty() {
+            self.nvim_keymaps = loaded;
         }
+        Ok(())
+    }
+
+    pub fn current_keymap(&self) -> Option<&Keymap> {
+        self.nvim_keymaps.iter().find(|k| k.lhs == self.target_text)
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> bool {
-        self.shift_pressed = key.modifiers.contains(KeyModifiers::SHIFT);
-        self.ctrl_pressed = key.modifiers.contains(KeyModifiers::CONTROL);
-        self.alt_pressed = key.modifiers.contains(KeyModifiers::ALT);
+        if self.track_modifier_keys(&key) {
+            return true;
+        }
+        let key = self.emulate_key(self.apply_caps_lock(key));
+        let held = |a, b| self.held_modifiers.contains(&a) || self.held_modifiers.contains(&b);
+        self.shift_pressed = key.modifiers.contains(KeyModifiers::SHIFT)
+            || held(ModifierKeyCode::LeftShift, ModifierKeyCode::RightShift);
+        self.ctrl_pressed = key.modifiers.contains(KeyModifiers::CONTROL)
+            || held(ModifierKeyCode::LeftControl, ModifierKeyCode::RightControl);
+        self.alt_pressed = key.modifiers.contains(KeyModifiers::ALT)
+            || held(ModifierKeyCode::LeftAlt, ModifierKeyCode::RightAlt);
+        self.key_modifiers = self.guess_modifier_keys(&key);
 
         let display = self.create_key_display(&key);
         if !display.is_empty() {
@@ -96,6 +203,8 @@ impl App {
             AppMode::Menu => self.handle_menu_key(key),
             AppMode::Settings => self.handle_settings_key(key),
             AppMode::About => self.handle_about_key(key),
+            AppMode::History => self.handle_history_key(key),
+            AppMode::Results => self.handle_results_key(key),
             AppMode::CountSelection => self.handle_count_selection_key(key),
             AppMode::WordsCommandsMenu
             | AppMode::WordsMenu
@@ -105,6 +214,129 @@ impl App {
         }
     }
 
+    // Modifier and Caps Lock events only update what is lit on the keyboard,
+    // and releases (sent by enhanced terminals and on Windows) are not typing
+    fn track_modifier_keys(&mut self, key: &KeyEvent) -> bool {
+        if self.keyboard_enhanced && key.code != KeyCode::CapsLock {
+            self.caps_lock = key.state.contains(KeyEventState::CAPS_LOCK);
+        }
+
+        match key.code {
+            KeyCode::Modifier(modifier) => {
+                if key.kind == KeyEventKind::Release {
+                    self.held_modifiers.remove(&modifier);
+                } else {
+                    self.held_modifiers.insert(modifier);
+                }
+                let held =
+                    |a, b| self.held_modifiers.contains(&a) || self.held_modifiers.contains(&b);
+                self.shift_pressed = held(ModifierKeyCode::LeftShift, ModifierKeyCode::RightShift);
+                self.ctrl_pressed =
+                    held(ModifierKeyCode::LeftControl, ModifierKeyCode::RightControl);
+                self.alt_pressed = held(ModifierKeyCode::LeftAlt, ModifierKeyCode::RightAlt);
+                true
+            }
+            KeyCode::CapsLock => {
+                if key.kind == KeyEventKind::Press {
+                    self.caps_lock = !self.caps_lock;
+                }
+                true
+            }
+            _ => key.kind == KeyEventKind::Release,
+        }
+    }
+
+    // Terminals that report every key as an escape code send letters without
+    // Caps Lock applied and only flag it in the event state
+    fn apply_caps_lock(&self, key: KeyEvent) -> KeyEvent {
+        if self.keyboard_enhanced
+            && key.state.contains(KeyEventState::CAPS_LOCK)
+            && let KeyCode::Char(c) = key.code
+            && c.is_alphabetic()
+        {
+            let swapped = if c.is_uppercase() {
+                c.to_lowercase().next()
+            } else {
+                c.to_uppercase().next()
+            };
+            return KeyEvent {
+                code: KeyCode::Char(swapped.unwrap_or(c)),
+                ..key
+            };
+        }
+        key
+    }
+
+    // Without enhanced reporting only the modifiers folded into a key event
+    // are known: a shifted character lights the Shift of the other hand, as
+    // in touch typing, and Ctrl/Alt light up on both sides
+    fn guess_modifier_keys(&self, key: &KeyEvent) -> ModifierState {
+        let shifted = match key.code {
+            KeyCode::Char(c) => {
+                key.modifiers.contains(KeyModifiers::SHIFT) || self.keyboard_layout.needs_shift(c)
+            }
+            KeyCode::BackTab => true,
+            _ => key.modifiers.contains(KeyModifiers::SHIFT),
+        };
+        let left_hand = match key.code {
+            KeyCode::Char(c) => self
+                .keyboard_layout
+                .key_for(c)
+                .and_then(|k| k.finger)
+                .is_some_and(|f| f.is_left()),
+            _ => true,
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        ModifierState {
+            left_shift: shifted && !left_hand,
+            right_shift: shifted && left_hand,
+            caps_lock: false,
+            left_ctrl: ctrl,
+            right_ctrl: ctrl,
+            left_alt: alt,
+            right_alt: alt,
+            super_key: key.modifiers.contains(KeyModifiers::SUPER),
+        }
+    }
+
+    pub fn modifier_state(&self) -> ModifierState {
+        if self.keyboard_enhanced {
+            let held = |m| self.held_modifiers.contains(&m);
+            return ModifierState {
+                left_shift: held(ModifierKeyCode::LeftShift),
+                right_shift: held(ModifierKeyCode::RightShift),
+                caps_lock: self.caps_lock,
+                left_ctrl: held(ModifierKeyCode::LeftControl),
+                right_ctrl: held(ModifierKeyCode::RightControl),
+                left_alt: held(ModifierKeyCode::LeftAlt),
+                right_alt: held(ModifierKeyCode::RightAlt),
+                super_key: held(ModifierKeyCode::LeftSuper) || held(ModifierKeyCode::RightSuper),
+            };
+        }
+        match &self.last_pressed_key {
+            Some(pk) if pk.is_active() => self.key_modifiers,
+            _ => ModifierState::default(),
+        }
+    }
+
+    // With emulation on, keys arrive in the system layout and are translated
+    // to what the selected layout types on the same physical key
+    fn emulate_key(&self, key: KeyEvent) -> KeyEvent {
+        if let Some(source) = &self.emulation_source
+            && self.is_practice_mode()
+            && let KeyCode::Char(c) = key.code
+            && let Some(mapped) = self.keyboard_layout.remap_from(source, c)
+        {
+            return KeyEvent {
+                code: KeyCode::Char(mapped),
+                ..key
+            };
+        }
+        key
+    }
+
     fn create_key_display(&self, key: &KeyEvent) -> String {
         match key.code {
             KeyCode::Char(c) => {
@@ -141,7 +373,7 @@ impl App {
         match key.code {
             KeyCode::Char('q') | KeyCode::Esc => return false,
             KeyCode::Up if self.menu_selected > 0 => self.menu_selected -= 1,
-            KeyCode::Down if self.menu_selected < 4 => self.menu_selected += 1,
+            KeyCode::Down if self.menu_selected < 6 => self.menu_selected += 1,
             KeyCode::Enter => match self.menu_selected {
                 0 => {
                     self.mode = AppMode::WordsCommandsMenu;
@@ -155,14 +387,19 @@ impl App {
                     self.mode = AppMode::CodeTestMenu;
                     self.submenu_selected = 0;
                 }
-                3 => {
+                3 => self.show_count_selection(AppMode::CustomFile),
+                4 => {
+                    self.mode = AppMode::History;
+                    self.history_scroll = 0;
+                }
+                5 => {
                     self.mode = AppMode::Settings;
                     self.submenu_selected = 0;
                 }
-                4 => {
+                6 => {
                     self.mode = AppMode::About;
                 }
-                5 => return false,
+                7 => return false,
                 _ => {}
             },
             _ => {}
@@ -174,18 +411,22 @@ impl App {
         match key.code {
             KeyCode::Esc => self.mode = AppMode::Menu,
             KeyCode::Up if self.submenu_selected > 0 => self.submenu_selected -= 1,
-            KeyCode::Down if self.submenu_selected < 1 => self.submenu_selected += 1,
+            KeyCode::Down if self.submenu_selected < 4 => self.submenu_selected += 1,
             KeyCode::Left | KeyCode::Right | KeyCode::Enter => match self.submenu_selected {
                 0 => {
-                    self.keyboard_layout = match self.keyboard_layout {
-                        KeyboardLayout::Dvorak => KeyboardLayout::Qwerty,
-                        KeyboardLayout::Qwerty => KeyboardLayout::Dvorak,
+                    let count = self.available_layouts.len();
+                    let current = self
+                        .available_layouts
+                        .iter()
+                        .position(|l| l.id == self.keyboard_layout.id)
+                        .unwrap_or(0);
+                    let next = if key.code == KeyCode::Left {
+                        (current + count - 1) % count
+                    } else {
+                        (current + 1) % count
                     };
-                    self.current_layout_name = match self.keyboard_layout {
-                        KeyboardLayout::Dvorak => "Dvorak Programmer",
-                        KeyboardLayout::Qwerty => "QWERTY",
-                    }
-                    .to_string();
+                    self.keyboard_layout = self.available_layouts[next].clone();
+                    self.refresh_history_key_stats();
                 }
                 1 => {
                     let new_lang = if self.translations.main_menu == "Main Menu" {
@@ -197,6 +438,20 @@ impl App {
                     };
                     self.translations = Translations::new(new_lang);
                 }
+                2 => self.mistake_policy = self.mistake_policy.next(),
+                3 => self.skip_indentation = !self.skip_indentation,
+                4 => {
+                    let sources: Vec<KeyboardLayout> = self
+                        .available_layouts
+                        .iter()
+                        .filter(|l| {
+                            l.id != self.keyboard_layout.id
+                                && l.same_geometry(&self.keyboard_layout)
+                        })
+                        .cloned()
+                        .collect();
+                    self.emulation_source = Self::cycle_filter(&sources, &self.emulation_source);
+                }
                 _ => {}
             },
             _ => {}
@@ -212,10 +467,55 @@ impl App {
         true
     }
 
+    fn handle_history_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Esc => self.mode = AppMode::Menu,
+            KeyCode::Up if self.history_scroll > 0 => self.history_scroll -= 1,
+            KeyCode::Down => {
+                let records = self.history.query(
+                    self.history_mode_filter.as_ref(),
+                    self.history_layout_filter.as_deref(),
+                    self.history_sort,
+                );
+                if self.history_scroll + 1 < records.len() {
+                    self.history_scroll += 1;
+                }
+            }
+            KeyCode::Char('s') => {
+                self.history_sort = self.history_sort.next();
+                self.history_scroll = 0;
+            }
+            KeyCode::Char('m') => {
+                self.history_mode_filter =
+                    Self::cycle_filter(&self.history.modes(), &self.history_mode_filter);
+                self.history_scroll = 0;
+            }
+            KeyCode::Char('l') => {
+                self.history_layout_filter =
+                    Self::cycle_filter(&self.history.layouts(), &self.history_layout_filter);
+                self.history_scroll = 0;
+            }
+            _ => {}
+        }
+        true
+    }
+
+    // None -> first option -> ... -> last option -> None
+    fn cycle_filter<T: Clone + PartialEq>(options: &[T], current: &Option<T>) -> Option<T> {
+        let next = match current {
+            None => 0,
+            Some(value) => match options.iter().position(|o| o == value) {
+                Some(i) => i + 1,
+                None => 0,
+            },
+        };
+        options.get(next).cloned()
+    }
+
     fn handle_submenu_key(&mut self, key: KeyEvent) -> bool {
         let max_items = match self.mode {
-            AppMode::WordsCommandsMenu => 2,
-            AppMode::SentencesMenu => 2,
+            AppMode::WordsCommandsMenu => 5,
+            AppMode::SentencesMenu => 7,
             _ => 4,
         };
 
@@ -232,6 +532,9 @@ impl App {
                             self.mode = AppMode::WordsMenu;
                             self.submenu_selected = 0;
                         }
+                        3 => self.show_count_selection(AppMode::WordsWeakKeys),
+                        4 => self.show_count_selection(AppMode::CustomKeymaps),
+                        5 => self.show_count_selection(AppMode::VimMotions),
                         _ => {}
                     }
                 } else if self.mode == AppMode::SentencesMenu {
@@ -239,6 +542,11 @@ impl App {
                         0 => AppMode::SentencesNormal,
                         1 => AppMode::SentencesDvorak,
                         2 => AppMode::SentencesQwerty,
+                        3 => AppMode::SentencesColemak,
+                        4 => AppMode::SentencesColemakDh,
+                        5 => AppMode::SentencesWorkman,
+                        6 => AppMode::SentencesNorman,
+                        7 => AppMode::SentencesJapanese,
                         _ => AppMode::SentencesNormal,
                     };
                     self.show_count_selection(target_mode);
@@ -282,7 +590,12 @@ impl App {
                 self.pending_mode = None;
             }
             KeyCode::Up if self.count_selected > 0 => self.count_selected -= 1,
-            KeyCode::Down if self.count_selected < 4 => self.count_selected += 1,
+            KeyCode::Down if self.count_selected < 8 => self.count_selected += 1,
+            // Counts (0-4) and time limits (5-8) are shown as two columns
+            KeyCode::Left if self.count_selected >= 5 => self.count_selected -= 5,
+            KeyCode::Right if self.count_selected < 5 => {
+                self.count_selected = (self.count_selected + 5).min(8)
+            }
             KeyCode::Enter => {
                 self.exercise_count = match self.count_selected {
                     0 => ExerciseCount::All,
@@ -290,11 +603,17 @@ impl App {
                     2 => ExerciseCount::Count25,
                     3 => ExerciseCount::Count50,
                     4 => ExerciseCount::Count100,
+                    5 => ExerciseCount::Seconds15,
+                    6 => ExerciseCount::Seconds30,
+                    7 => ExerciseCount::Seconds60,
+                    8 => ExerciseCount::Seconds120,
                     _ => ExerciseCount::All,
                 };
 
                 if let Some(target) = self.pending_mode.take() {
                     self.exercises_completed = 0;
+                    self.exercises_failed = 0;
+                    self.stats.reset();
                     self.mode = target;
                     self.start_exercise();
                 }
@@ -305,23 +624,62 @@ impl App {
     }
 
     fn handle_practice_key(&mut self, key: KeyEvent) -> bool {
+        // Keymap targets are matched as whole chords, modifiers included. Esc
+        // and F2 keep their usual meaning unless they are the next chord of
+        // the mapping, as in <Esc><Esc> or <F2>
+        if self.mode == AppMode::CustomKeymaps
+            && (!matches!(key.code, KeyCode::Esc | KeyCode::F(2))
+                || self
+                    .target_chords
+                    .get(self.current_key_index)
+                    .is_some_and(|chord| chord.matches(&key)))
+        {
+            self.process_chord(key);
+            return true;
+        }
+
+        // Esc belongs to the simulated editor until it is back in normal mode
+        // with nothing pending
+        if self.mode == AppMode::VimMotions
+            && !matches!(key.code, KeyCode::F(2))
+            && !(key.code == KeyCode::Esc && self.vim_buffer.as_ref().is_none_or(|b| b.is_idle()))
+        {
+            self.process_vim_key(key);
+            return true;
+        }
+
         match key.code {
             KeyCode::Esc => {
+                self.finish_session();
                 self.mode = AppMode::Menu;
                 self.stats.reset();
                 self.last_pressed_key = None;
                 self.exercises_completed = 0;
+                self.exercises_failed = 0;
                 return true;
             }
+            KeyCode::F(2) => {
+                self.heatmap_mode = self.heatmap_mode.next();
+                self.refresh_history_key_stats();
+            }
+            KeyCode::Char(c) if self.mode == AppMode::SentencesJapanese => {
+                self.process_romaji_char(c);
+            }
             KeyCode::Char(c) => {
                 self.process_typed_char(c);
             }
-            KeyCode::Backspace => {
-                if !self.typed_text.is_empty() {
-                    self.typed_text.pop();
-                    if self.current_key_index > 0 {
-                        self.current_key_index -= 1;
-                    }
+            KeyCode::Backspace if !self.pending_input.is_empty() => {
+                self.pending_input.pop();
+            }
+            KeyCode::Backspace if !self.typed_text.is_empty() => {
+                pop_grapheme(&mut self.typed_text);
+                if self.current_key_index > 0 {
+                    self.current_key_index -= 1;
+                }
+                // A wrong character typed freely is gone once erased, so it
+                // no longer marks the position
+                if self.mistake_policy == MistakePolicy::Free {
+                    self.typed_errors.remove(&self.current_key_index);
                 }
             }
             KeyCode::Enter => {
@@ -335,46 +693,429 @@ impl App {
         true
     }
 
-    fn process_typed_char(&mut self, c: char) {
+    pub fn time_remaining(&self) -> Option<Duration> {
+        let limit = self.exercise_count.to_duration()?;
+        Some(limit.saturating_sub(self.stats.elapsed()))
+    }
+
+    pub fn on_tick(&mut self) {
+        if self
+            .last_pressed_key
+            .as_ref()
+            .is_some_and(|pk| !pk.is_active())
+        {
+            self.last_pressed_key = None;
+        }
+
+        if self.is_practice_mode() && self.time_remaining() == Some(Duration::ZERO) {
+            self.end_timed_session();
+        }
+    }
+
+    pub fn is_practice_mode(&self) -> bool {
+        !matches!(
+            self.mode,
+            AppMode::Menu
+                | AppMode::Settings
+                | AppMode::About
+                | AppMode::History
+                | AppMode::Results
+                | AppMode::CountSelection
+                | AppMode::WordsCommandsMenu
+                | AppMode::WordsMenu
+                | AppMode::CodeTestMenu
+                | AppMode::SentencesMenu
+        )
+    }
+
+    // The window is closed at exactly start + limit so the final WPM does not
+    // depend on how late the tick or keystroke arrived
+    fn end_timed_session(&mut self) {
+        if let (Some(start), Some(limit)) =
+            (self.stats.start_time, self.exercise_count.to_duration())
+        {
+            self.stats.finish(start + limit);
+        }
+        self.show_results();
+    }
+
+    // Starts the clock on the first keystroke; false once a timed session has
+    // run out, in which case the keystroke is dropped
+    fn begin_keystroke(&mut self) -> bool {
+        if self.time_remaining() == Some(Duration::ZERO) {
+            self.end_timed_session();
+            return false;
+        }
+
         if self.stats.start_time.is_none() {
             self.stats.start_time = Some(Instant::now());
         }
+        true
+    }
 
-        if self.target_text.is_empty() {
+    fn process_typed_char(&mut self, c: char) {
+        if !self.begin_keystroke() || self.target_text.is_empty() {
             return;
         }
 
-        let expected = self.target_text.chars().nth(self.current_key_index);
-
-        if let Some(expected_char) = expected {
-            if c == expected_char {
-                self.typed_text.push(c);
-                self.current_key_index += 1;
-                self.stats.correct += 1;
+        // With free typing the cursor can sit past the end on a wrong text
+        let Some(expected) = grapheme_at(&self.target_text, self.current_key_index) else {
+            return;
+        };
+        let expected = expected.to_string();
 
-                if self.current_key_index >= self.target_text.len() {
-                    self.stats.total_chars += self.target_text.len() as u32;
-                    self.exercises_completed += 1;
+        // A cluster made of several chars (a decomposed accent, a flag, an
+        // emoji sequence) is typed one char at a time and only counts once
+        // it is complete
+        let mut input = std::mem::take(&mut self.pending_input);
+        input.push(c);
+        if input != expected && expected.starts_with(&input) {
+            self.pending_input = input;
+            return;
+        }
 
-                    if let Some(limit) = self.exercise_count.to_usize() {
-                        if self.exercises_completed >= limit {
-                            self.mode = AppMode::Menu;
-                            return;
-                        }
-                    }
+        // The whole cluster is one keystroke on its base character, right or
+        // wrong as a unit, so a correct e + U+0301 is not an error on e
+        let expected_char = expected.chars().next().unwrap_or(c);
+        let typed_char = if input == expected { expected_char } else { c };
+        self.stats
+            .record_key(expected_char, typed_char, input != expected);
 
-                    self.next_exercise();
+        if input == expected {
+            self.stats.correct += 1;
+        } else {
+            self.stats.errors += 1;
+            self.typed_errors.insert(self.current_key_index, c);
+            match self.mistake_policy {
+                MistakePolicy::Block => return,
+                MistakePolicy::Free => input = c.to_string(),
+                MistakePolicy::SuddenDeath => {
+                    self.fail_exercise();
+                    return;
                 }
-            } else {
+            }
+        }
+
+        self.typed_text.push_str(&input);
+        self.current_key_index += 1;
+
+        if input == "\n" && expected == "\n" && self.skip_indentation {
+            self.skip_leading_indentation();
+        }
+
+        if self.typed_text == self.target_text {
+            self.complete_exercise(grapheme_count(&self.target_text));
+        }
+    }
+
+    // Like an editor's autoindent: the indentation after a newline is filled
+    // in without being counted as typed
+    fn skip_leading_indentation(&mut self) {
+        let indentation: Vec<&str> = graphemes(&self.target_text)
+            .into_iter()
+            .skip(self.current_key_index)
+            .take_while(|g| *g == " " || *g == "\t")
+            .collect();
+        self.current_key_index += indentation.len();
+        self.typed_text.push_str(&indentation.concat());
+    }
+
+    // Character the user has to type next, used to guide them on the keyboard
+    pub fn next_expected_char(&self) -> Option<char> {
+        if !self.is_practice_mode() {
+            return None;
+        }
+        match self.mode {
+            AppMode::VimMotions => None,
+            AppMode::CustomKeymaps => self
+                .target_chords
+                .get(self.current_key_index)
+                .and_then(|chord| chord.base_char()),
+            AppMode::SentencesJapanese => {
+                let rest: Vec<char> = graphemes(&self.target_text)
+                    .into_iter()
+                    .skip(self.current_key_index)
+                    .flat_map(|g| g.chars())
+                    .collect();
+                romaji::hint(&rest, &self.pending_input)?
+                    .chars()
+                    .nth(self.pending_input.chars().count())
+            }
+            _ => grapheme_at(&self.target_text, self.current_key_index)?
+                .strip_prefix(self.pending_input.as_str())?
+                .chars()
+                .next(),
+        }
+    }
+
+    pub fn code_language(&self) -> Option<&'static str> {
+        match self.mode {
+            AppMode::CodeTestLua => Some("lua"),
+            AppMode::CodeTestRuby => Some("ruby"),
+            AppMode::CodeTestRust => Some("rust"),
+            AppMode::CodeTestTypescript => Some("typescript"),
+            AppMode::CodeTestPython => Some("python"),
+            _ => None,
+        }
+    }
+
+    pub fn is_code_exercise(&self) -> bool {
+        self.code_language().is_some()
+            || (self.mode == AppMode::CustomFile && self.target_text.contains('\n'))
+    }
+
+    fn process_chord(&mut self, key: KeyEvent) {
+        if matches!(key.code, KeyCode::Modifier(_)) || !self.begin_keystroke() {
+            return;
+        }
+
+        let Some(expected) = self.target_chords.get(self.current_key_index).cloned() else {
+            return;
+        };
+        let matched = expected.matches(&key);
+
+        if let Some(expected_char) = expected.base_char() {
+            let typed = KeyChord::new(key.code, key.modifiers)
+                .base_char()
+                .unwrap_or('\0');
+            self.stats.record_key(expected_char, typed, !matched);
+        }
+
+        if matched {
+            self.typed_text.push_str(&expected.display());
+            self.current_key_index += 1;
+            self.stats.correct += 1;
+
+            if self.current_key_index >= self.target_chords.len() {
+                self.complete_exercise(self.target_chords.len());
+            }
+        } else {
+            self.stats.errors += 1;
+            if self.mistake_policy == MistakePolicy::SuddenDeath {
+                self.fail_exercise();
+            }
+        }
+    }
+
+    // The reading is converted as romaji comes in; pending_input holds the
+    // romaji typed towards the next kana. Free typing behaves like block here
+    fn process_romaji_char(&mut self, c: char) {
+        if !self.begin_keystroke() || self.target_text.is_empty() {
+            return;
+        }
+
+        let rest: Vec<char> = graphemes(&self.target_text)
+            .into_iter()
+            .skip(self.current_key_index)
+            .flat_map(|g| g.chars())
+            .collect();
+
+        match romaji::feed(&rest, &self.pending_input, c) {
+            RomajiStep::Accepted { converted, pending } => {
+                self.stats.record_keystroke(c, c);
+                self.stats.correct += 1;
+                let kana: String = rest.iter().take(converted).collect();
+                self.typed_text.push_str(&kana);
+                self.current_key_index += converted;
+                self.pending_input = pending;
+            }
+            RomajiStep::Rejected { expected } => {
+                self.stats.record_keystroke(expected.unwrap_or(c), c);
                 self.stats.errors += 1;
+                self.typed_errors.insert(self.current_key_index, c);
+                if self.mistake_policy == MistakePolicy::SuddenDeath {
+                    self.fail_exercise();
+                }
+                return;
+            }
+        }
+
+        if self.typed_text == self.target_text {
+            self.complete_exercise(grapheme_count(&self.target_text));
+        }
+    }
+
+    // The rest of the exercise is dropped; it still counts towards the
+    // exercise count but adds no characters
+    fn fail_exercise(&mut self) {
+        self.exercises_failed += 1;
+        self.complete_exercise(0);
+    }
+
+    fn process_vim_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::F(5) {
+            self.restart_vim_challenge();
+            return;
+        }
+        if matches!(key.code, KeyCode::Modifier(_)) || !self.begin_keystroke() {
+            return;
+        }
+        let (Some(challenge), Some(buffer)) = (&self.vim_challenge, &mut self.vim_buffer) else {
+            return;
+        };
+
+        buffer.handle_key(key.code);
+        self.vim_keystrokes += 1;
+
+        if challenge.is_solved(buffer) {
+            // Keystrokes within par count as correct, every extra one as an error
+            let par = challenge.par();
+            self.stats.correct += self.vim_keystrokes.min(par) as u32;
+            self.stats.errors += self.vim_keystrokes.saturating_sub(par) as u32;
+            self.vim_last_score = Some((self.vim_keystrokes, challenge.clone()));
+            self.complete_exercise(par);
+        }
+    }
+
+    fn restart_vim_challenge(&mut self) {
+        self.vim_buffer = self.vim_challenge.as_ref().map(|c| c.buffer());
+        self.vim_keystrokes = 0;
+    }
+
+    fn complete_exercise(&mut self, length: usize) {
+        self.stats.total_chars += length as u32;
+        self.exercises_completed += 1;
+
+        if let Some(limit) = self.exercise_count.to_usize()
+            && self.exercises_completed >= limit
+        {
+            self.show_results();
+            return;
+        }
+
+        self.next_exercise();
+    }
+
+    fn show_results(&mut self) {
+        let previous_best = self
+            .history
+            .best_wpm(&self.mode, &self.keyboard_layout.name);
+
+        match self.finish_session() {
+            Some(record) => {
+                self.results = Some(SessionResults {
+                    record,
+                    exercise_count: self.exercise_count,
+                    slowest_keys: self.stats.slowest_keys(3),
+                    previous_best,
+                    exercises_failed: self.exercises_failed,
+                });
+                self.mode = AppMode::Results;
+                self.submenu_selected = 0;
             }
+            None => self.mode = AppMode::Menu,
         }
     }
 
+    fn handle_results_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Esc => self.mode = AppMode::Menu,
+            KeyCode::Up | KeyCode::Left if self.submenu_selected > 0 => self.submenu_selected -= 1,
+            KeyCode::Down | KeyCode::Right if self.submenu_selected < 1 => {
+                self.submenu_selected += 1
+            }
+            KeyCode::Enter => match self.submenu_selected {
+                0 => self.retry_session(),
+                _ => self.mode = AppMode::Menu,
+            },
+            _ => {}
+        }
+        true
+    }
+
+    fn retry_session(&mut self) {
+        if let Some(results) = &self.results {
+            self.exercise_count = results.exercise_count;
+            self.mistake_policy = results.record.mistake_policy;
+            self.mode = results.record.mode.clone();
+            self.exercises_completed = 0;
+            self.exercises_failed = 0;
+            self.stats.reset();
+            self.last_pressed_key = None;
+            self.start_exercise();
+        }
+    }
+
+    fn finish_session(&mut self) -> Option<SessionRecord> {
+        self.stats.finish(Instant::now());
+        // Sessions left within a second of the first key would record absurd WPM
+        if self.stats.correct + self.stats.errors == 0
+            || self.stats.elapsed_secs() < MIN_SESSION_SECS
+        {
+            return None;
+        }
+
+        let record = SessionRecord {
+            mode: self.mode.clone(),
+            layout: self.keyboard_layout.name.clone(),
+            exercise_count: self.exercise_count.display().to_string(),
+            exercises_completed: self.exercises_completed,
+            wpm: self.stats.calculate_wpm(),
+            raw_wpm: self.stats.calculate_raw_wpm(),
+            accuracy: self.stats.calculate_accuracy(),
+            correct: self.stats.correct,
+            errors: self.stats.errors,
+            duration_secs: self.stats.elapsed_secs(),
+            timestamp: SessionRecord::now(),
+            key_stats: self.stats.key_stats(),
+            mistake_policy: self.mistake_policy,
+        };
+        let _ = self.history.append(record.clone());
+        self.refresh_history_key_stats();
+        Some(record)
+    }
+
+    // Session stats are merged on top of the saved history for this layout so
+    // the drill re-weights after every exercise
+    fn weak_keys_exercise(&self) -> String {
+        let mut key_stats = self.history_key_stats.clone();
+        for stat in self.stats.key_stats() {
+            match key_stats.iter_mut().find(|s| s.key == stat.key) {
+                Some(existing) => existing.merge(&stat),
+                None => key_stats.push(stat),
+            }
+        }
+
+        build_weak_keys_exercise(
+            &get_weak_keys_word_pool(),
+            &key_stats,
+            &self.stats.missed_bigrams(),
+        )
+    }
+
     pub fn start_exercise(&mut self) {
+        if self.mode == AppMode::VimMotions {
+            let challenges = get_vim_challenges();
+            let challenge = challenges[rand::thread_rng().gen_range(0..challenges.len())].clone();
+            self.target_text = challenge.description.to_string();
+            self.vim_challenge = Some(challenge);
+            self.restart_vim_challenge();
+            if self.exercises_completed == 0 {
+                self.vim_last_score = None;
+            }
+            self.typed_text.clear();
+            self.pending_input.clear();
+            self.typed_errors.clear();
+            self.current_key_index = 0;
+            return;
+        }
+
+        if self.mode == AppMode::SentencesJapanese {
+            let sentences = get_sentences_japanese();
+            let sentence = sentences[rand::thread_rng().gen_range(0..sentences.len())].clone();
+            self.target_text = sentence.reading.to_string();
+            self.japanese_sentence = Some(sentence);
+            self.typed_text.clear();
+            self.pending_input.clear();
+            self.typed_errors.clear();
+            self.current_key_index = 0;
+            return;
+        }
+
         let words = match self.mode {
             AppMode::VimCommands => get_vim_commands(),
             AppMode::WordsSimple => get_simple_words(),
+            AppMode::WordsWeakKeys => vec![self.weak_keys_exercise()],
             AppMode::WordsLua => get_lua_words(),
             AppMode::WordsRuby => get_ruby_words(),
             AppMode::WordsRust => get_rust_words(),
@@ -383,12 +1124,17 @@ impl App {
             AppMode::SentencesNormal => get_sentences_normal(),
             AppMode::SentencesDvorak => get_sentences_dvorak(),
             AppMode::SentencesQwerty => get_sentences_qwerty(),
+            AppMode::SentencesColemak => get_sentences_colemak(),
+            AppMode::SentencesColemakDh => get_sentences_colemak_dh(),
+            AppMode::SentencesWorkman => get_sentences_workman(),
+            AppMode::SentencesNorman => get_sentences_norman(),
             AppMode::CodeTestLua => get_code_tests("lua"),
             AppMode::CodeTestRuby => get_code_tests("ruby"),
             AppMode::CodeTestRust => get_code_tests("rust"),
             AppMode::CodeTestTypescript => get_code_tests("typescript"),
             AppMode::CodeTestPython => get_code_tests("python"),
-            AppMode::CustomKeymaps => self.nvim_keymaps.clone(),
+            AppMode::CustomKeymaps => self.nvim_keymaps.iter().map(|k| k.lhs.clone()).collect(),
+            AppMode::CustomFile => self.custom_exercises.clone(),
             _ => vec![],
         };
 
@@ -396,9 +1142,19 @@ impl App {
             let mut rng = rand::thread_rng();
             let idx = rng.gen_range(0..words.len());
             self.target_text = words[idx].clone();
+        } else {
+            self.target_text.clear();
         }
 
+        self.target_chords = if self.mode == AppMode::CustomKeymaps {
+            parse_notation(&self.target_text, &self.keymap_leader)
+        } else {
+            Vec::new()
+        };
+
         self.typed_text.clear();
+        self.pending_input.clear();
+        self.typed_errors.clear();
         self.current_key_index = 0;
     }
 
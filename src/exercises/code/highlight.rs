@@ -0,0 +1,152 @@
+use crate::exercises::{
+    get_lua_words, get_python_words, get_ruby_words, get_rust_words, get_typescript_words,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    Keyword,
+    Function,
+    String,
+    Comment,
+    Number,
+    Punctuation,
+    Plain,
+}
+
+struct Syntax {
+    keywords: Vec<String>,
+    line_comment: &'static str,
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+}
+
+fn syntax_for(lang: &str) -> Option<Syntax> {
+    let syntax = match lang {
+        "lua" => Syntax {
+            keywords: get_lua_words(),
+            line_comment: "--",
+            block_comment: Some(("--[[", "]]")),
+            quotes: &['"', '\''],
+        },
+        "ruby" => Syntax {
+            keywords: get_ruby_words(),
+            line_comment: "#",
+            block_comment: None,
+            quotes: &['"', '\''],
+        },
+        "rust" => Syntax {
+            keywords: get_rust_words(),
+            line_comment: "//",
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"'],
+        },
+        "typescript" => Syntax {
+            keywords: get_typescript_words(),
+            line_comment: "//",
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"', '\'', '`'],
+        },
+        "python" => Syntax {
+            keywords: get_python_words(),
+            line_comment: "#",
+            block_comment: None,
+            quotes: &['"', '\''],
+        },
+        _ => return None,
+    };
+    Some(syntax)
+}
+
+fn starts_with_at(chars: &[char], i: usize, pattern: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    chars[i..].starts_with(&pattern)
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// Classifies every character of the snippet so the renderer can colour it
+// without caring about token boundaries; None for unknown languages
+pub fn highlight_code(code: &str, lang: &str) -> Option<Vec<TokenKind>> {
+    let syntax = syntax_for(lang)?;
+    let chars: Vec<char> = code.chars().collect();
+    let mut kinds = vec![TokenKind::Plain; chars.len()];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        let kind = if let Some((open, close)) = syntax.block_comment
+            && starts_with_at(&chars, i, open)
+        {
+            i += open.chars().count();
+            while i < chars.len() && !starts_with_at(&chars, i, close) {
+                i += 1;
+            }
+            i = (i + close.chars().count()).min(chars.len());
+            TokenKind::Comment
+        } else if starts_with_at(&chars, i, syntax.line_comment) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            TokenKind::Comment
+        } else if syntax.quotes.contains(&c) {
+            i += 1;
+            while i < chars.len() && chars[i] != c && chars[i] != '\n' {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i = (i + 1).min(chars.len());
+            TokenKind::String
+        } else if lang == "rust" && c == '\'' {
+            // 'a' and '\n' are chars, anything else after a quote is a lifetime
+            let len = if chars.get(i + 1) == Some(&'\\') {
+                4
+            } else {
+                3
+            };
+            if chars.get(i + len - 1) == Some(&'\'') {
+                i += len;
+                TokenKind::String
+            } else {
+                i += 1;
+                while i < chars.len() && is_ident(chars[i]) {
+                    i += 1;
+                }
+                TokenKind::Keyword
+            }
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (is_ident(chars[i]) || chars[i] == '.') {
+                // Keep ranges like 1..n apart from the number
+                if chars[i] == '.' && chars.get(i + 1) == Some(&'.') {
+                    break;
+                }
+                i += 1;
+            }
+            TokenKind::Number
+        } else if is_ident(c) {
+            while i < chars.len() && is_ident(chars[i]) {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if syntax.keywords.contains(&word) {
+                TokenKind::Keyword
+            } else if matches!(chars.get(i), Some('(') | Some('!')) {
+                TokenKind::Function
+            } else {
+                TokenKind::Plain
+            }
+        } else if c.is_whitespace() {
+            i += 1;
+            TokenKind::Plain
+        } else {
+            i += 1;
+            TokenKind::Punctuation
+        };
+
+        kinds[start..i].fill(kind);
+    }
+
+    Some(kinds)
+}
@@ -1,5 +1,5 @@
 pub fn get_lua_code_tests() -> Vec<String> {
-    vec![
+    [
         r#"function fibonacci(n)
   if n <= 1 then
     return n
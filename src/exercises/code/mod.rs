@@ -1,3 +1,4 @@
+pub mod highlight;
 pub mod lua;
 pub mod python;
 pub mod ruby;
@@ -1,5 +1,5 @@
 pub fn get_python_code_tests() -> Vec<String> {
-    vec![
+    [
         r#"def fibonacci(n):
     if n <= 1:
         return n
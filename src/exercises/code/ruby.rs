@@ -1,5 +1,5 @@
 pub fn get_ruby_code_tests() -> Vec<String> {
-    vec![
+    [
         r#"def fibonacci(n)
   return n if n <= 1
   fibonacci(n - 1) + fibonacci(n - 2)
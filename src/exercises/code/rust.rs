@@ -1,5 +1,5 @@
 pub fn get_rust_code_tests() -> Vec<String> {
-    vec![
+    [
         r#"fn fibonacci(n: u32) -> u32 {
     if n <= 1 {
         return n;
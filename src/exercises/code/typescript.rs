@@ -1,5 +1,5 @@
 pub fn get_typescript_code_tests() -> Vec<String> {
-    vec![
+    [
         r#"function fibonacci(n: number): number {
   if (n <= 1) {
     return n;
@@ -0,0 +1,102 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const MAX_FILE_BYTES: u64 = 64 * 1024;
+
+pub(crate) const SKIPPED_DIRS: &[&str] = &["target", "node_modules", "dist", "build", "vendor"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SplitMode {
+    Auto,
+    Lines,
+    Blocks,
+    File,
+}
+
+impl SplitMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(SplitMode::Auto),
+            "lines" => Some(SplitMode::Lines),
+            "blocks" => Some(SplitMode::Blocks),
+            "file" => Some(SplitMode::File),
+            _ => None,
+        }
+    }
+
+    // Plain text files are word/sentence lists, anything else is treated as source code
+    fn resolve(&self, path: &Path) -> SplitMode {
+        match self {
+            SplitMode::Auto => match path.extension().and_then(|e| e.to_str()) {
+                None | Some("txt") => SplitMode::Lines,
+                Some("md") => SplitMode::Blocks,
+                Some(_) => SplitMode::File,
+            },
+            other => *other,
+        }
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .lines()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
+
+pub fn split_exercises(content: &str, split: SplitMode) -> Vec<String> {
+    let content = normalize(content);
+
+    match split {
+        SplitMode::Lines | SplitMode::Auto => content
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect(),
+        SplitMode::Blocks => content
+            .split("\n\n")
+            .map(|block| block.trim_matches('\n').to_string())
+            .filter(|block| !block.trim().is_empty())
+            .collect(),
+        SplitMode::File if content.trim().is_empty() => vec![],
+        SplitMode::File => vec![content],
+    }
+}
+
+pub fn load_exercises_from_file(path: &Path, split: SplitMode) -> io::Result<Vec<String>> {
+    let content = fs::read_to_string(path)?;
+    Ok(split_exercises(&content, split.resolve(path)))
+}
+
+// Walks the tree recursively, skipping hidden entries, build output and files
+// that are too large or not valid UTF-8
+pub fn load_exercises_from_dir(path: &Path, split: SplitMode) -> io::Result<Vec<String>> {
+    let mut entries: Vec<_> = fs::read_dir(path)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.path());
+
+    let mut exercises = Vec::new();
+    for entry in entries {
+        let entry_path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if !SKIPPED_DIRS.contains(&name.as_str()) {
+                exercises.extend(load_exercises_from_dir(&entry_path, split)?);
+            }
+        } else if file_type.is_file()
+            && entry.metadata()?.len() <= MAX_FILE_BYTES
+            && let Ok(file_exercises) = load_exercises_from_file(&entry_path, split)
+        {
+            exercises.extend(file_exercises);
+        }
+    }
+    Ok(exercises)
+}
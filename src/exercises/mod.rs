@@ -1,9 +1,16 @@
 pub mod code;
+pub mod custom;
 pub mod sentences;
 pub mod vim;
+pub mod vim_motions;
+pub mod weak_keys;
 pub mod words;
 
+pub use code::highlight::{TokenKind, highlight_code};
+pub use custom::{SplitMode, load_exercises_from_dir, load_exercises_from_file};
 pub use vim::get_vim_commands;
+pub use vim_motions::{VimChallenge, VimGoal, get_vim_challenges};
+pub use weak_keys::{build_weak_keys_exercise, get_weak_keys_word_pool};
 
 pub use words::lua::get_lua_words;
 pub use words::python::get_python_words;
@@ -12,9 +19,14 @@ pub use words::rust::get_rust_words;
 pub use words::simple::get_simple_words;
 pub use words::typescript::get_typescript_words;
 
+pub use sentences::colemak::get_sentences_colemak;
+pub use sentences::colemak_dh::get_sentences_colemak_dh;
 pub use sentences::dvorak::get_sentences_dvorak;
+pub use sentences::japanese::{JapaneseSentence, get_sentences_japanese};
 pub use sentences::normal::get_sentences_normal;
+pub use sentences::norman::get_sentences_norman;
 pub use sentences::qwerty::get_sentences_qwerty;
+pub use sentences::workman::get_sentences_workman;
 
 pub fn get_code_tests(lang: &str) -> Vec<String> {
     match lang {
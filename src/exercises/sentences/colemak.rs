@@ -0,0 +1,67 @@
+pub fn get_sentences_colemak() -> Vec<String> {
+    vec![
+        "arst arst arst",
+        "neio neio neio",
+        "arst neio arst",
+        "a r s t n e i o",
+        "arst dh neio",
+        "dh dh arst neio",
+        "ts ts ne ne",
+        "ra ra io io",
+        "sa ta na ea",
+        "oi ie ei oe",
+        "st st st",
+        "th th th",
+        "he he he",
+        "in in in",
+        "er er er",
+        "on on on",
+        "an an an",
+        "re re re",
+        "at at at",
+        "en en en",
+        "nd nd nd",
+        "the the the",
+        "and and and",
+        "her her her",
+        "ion ion ion",
+        "tea tea tea",
+        "star star star",
+        "rest rest rest",
+        "note note note",
+        "dish dish dish",
+        "a star",
+        "neat notes",
+        "the stone",
+        "rain and ice",
+        "a red hat",
+        "tried it",
+        "stone tiers",
+        "other ideas",
+        "north side",
+        "the station",
+        "hidden treats",
+        "send the note",
+        "she is here",
+        "rest and read",
+        "trade the seat",
+        "three tents",
+        "a sheet of notes",
+        "a hero reads",
+        "dinner is on",
+        "the rider and the horse",
+        "those ideas are neat",
+        "a tired rider rests",
+        "she and her sister",
+        "the senator stood here",
+        "ration the dinner",
+        "one thin stone",
+        "arst arst neio neio",
+        "dhn dhn ret ret",
+        "sent sent sent",
+        "ties ties ties",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
@@ -0,0 +1,65 @@
+pub fn get_sentences_colemak_dh() -> Vec<String> {
+    vec![
+        "arst arst arst",
+        "mneio mneio",
+        "arstg mneio",
+        "a r s t m n e i o",
+        "arstg arstg",
+        "gm gm arst neio",
+        "st st ne ne",
+        "ra ra io io",
+        "ga ma na ea",
+        "oi ie ei oe",
+        "st st st",
+        "me me me",
+        "in in in",
+        "er er er",
+        "on on on",
+        "an an an",
+        "re re re",
+        "at at at",
+        "en en en",
+        "ng ng ng",
+        "ing ing ing",
+        "men men men",
+        "min min min",
+        "ion ion ion",
+        "gain gain gain",
+        "star star star",
+        "rest rest rest",
+        "time time time",
+        "song song song",
+        "a tiger",
+        "string it",
+        "train the mentor",
+        "master notes",
+        "motion and rest",
+        "ignore the noise",
+        "a great storm",
+        "gain more time",
+        "mist in the air",
+        "stream it",
+        "a green sign",
+        "meet the master",
+        "rest more",
+        "store the items",
+        "the mentor sings",
+        "more green trees",
+        "a strong stone",
+        "sing a song",
+        "the tiger roams the stream",
+        "ignore the storm signs",
+        "a mentor trains a singer",
+        "store more items",
+        "the master is resting",
+        "migrate the storage",
+        "a rare green gem",
+        "arstg arstg mneio",
+        "gm gm gm gm",
+        "mint mint mint",
+        "ring ring ring",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
@@ -0,0 +1,76 @@
+#[derive(Debug, Clone, PartialEq)]
+pub struct JapaneseSentence {
+    pub text: &'static str,
+    // Hiragana reading that is typed in romaji
+    pub reading: &'static str,
+}
+
+pub fn get_sentences_japanese() -> Vec<JapaneseSentence> {
+    [
+        (
+            "日本語を勉強しています。",
+            "にほんごをべんきょうしています。",
+        ),
+        ("今日はいい天気ですね。", "きょうはいいてんきですね。"),
+        (
+            "プログラミングは楽しいです。",
+            "ぷろぐらみんぐはたのしいです。",
+        ),
+        ("毎朝コーヒーを飲みます。", "まいあさこーひーをのみます。"),
+        (
+            "東京駅で友達に会いました。",
+            "とうきょうえきでともだちにあいました。",
+        ),
+        (
+            "キーボードを変えてから速く打てるようになった。",
+            "きーぼーどをかえてからはやくうてるようになった。",
+        ),
+        (
+            "新しいエディタの設定を試しています。",
+            "あたらしいえでぃたのせっていをためしています。",
+        ),
+        (
+            "週末は図書館で本を読みます。",
+            "しゅうまつはとしょかんでほんをよみます。",
+        ),
+        (
+            "電車が遅れて会議に間に合わなかった。",
+            "でんしゃがおくれてかいぎにまにあわなかった。",
+        ),
+        ("失敗は成功のもと。", "しっぱいはせいこうのもと。"),
+        ("猿も木から落ちる。", "さるもきからおちる。"),
+        ("急がば回れ。", "いそがばまわれ。"),
+        (
+            "雨が降ったら家で映画を見よう。",
+            "あめがふったらいえでえいがをみよう。",
+        ),
+        (
+            "このバグは明日までに直します。",
+            "このばぐはあしたまでになおします。",
+        ),
+        (
+            "小さな変更でもテストを書きましょう。",
+            "ちいさなへんこうでもてすとをかきましょう。",
+        ),
+        (
+            "春になると桜がとてもきれいです。",
+            "はるになるとさくらがとてもきれいです。",
+        ),
+        ("千里の道も一歩から。", "せんりのみちもいっぽから。"),
+        (
+            "駅前の喫茶店で待っています。",
+            "えきまえのきっさてんでまっています。",
+        ),
+        (
+            "昨日は遅くまで仕事をしていました。",
+            "きのうはおそくまでしごとをしていました。",
+        ),
+        (
+            "毎日少しずつ練習すれば上手になります。",
+            "まいにちすこしずつれんしゅうすればじょうずになります。",
+        ),
+    ]
+    .iter()
+    .map(|(text, reading)| JapaneseSentence { text, reading })
+    .collect()
+}
@@ -1,3 +1,8 @@
+pub mod colemak;
+pub mod colemak_dh;
 pub mod dvorak;
+pub mod japanese;
 pub mod normal;
+pub mod norman;
 pub mod qwerty;
+pub mod workman;
@@ -0,0 +1,65 @@
+pub fn get_sentences_norman() -> Vec<String> {
+    vec![
+        "aset aset aset",
+        "nioh nioh nioh",
+        "aset nioh aset",
+        "a s e t n i o h",
+        "asetg ynioh",
+        "gy gy aset nioh",
+        "te te no no",
+        "sa sa oh oh",
+        "ta ha na ea",
+        "oi ie ei oe",
+        "th th th",
+        "he he he",
+        "in in in",
+        "on on on",
+        "an an an",
+        "ng ng ng",
+        "at at at",
+        "en en en",
+        "st st st",
+        "es es es",
+        "the the the",
+        "and and and",
+        "ing ing ing",
+        "one one one",
+        "hen hen hen",
+        "stay stay stay",
+        "easy easy easy",
+        "things things things",
+        "night night night",
+        "honest honest honest",
+        "the same",
+        "easy night",
+        "stay honest",
+        "thin ties",
+        "a yoga session",
+        "eighty signs",
+        "the haste",
+        "a noisy night",
+        "hot tea",
+        "those things",
+        "she sings",
+        "get the tent",
+        "a tiny sign",
+        "they stay",
+        "honest hosts",
+        "the tent is set",
+        "a neat song",
+        "his sister",
+        "the honest hostess sings",
+        "those eighty signs stay",
+        "she gets the shiny tea",
+        "stay in on a noisy night",
+        "the same thing again",
+        "a thin stone sits",
+        "asetg asetg ynioh",
+        "gy gy gy gy",
+        "tone tone tone",
+        "sign sign sign",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
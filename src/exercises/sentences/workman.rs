@@ -0,0 +1,66 @@
+pub fn get_sentences_workman() -> Vec<String> {
+    vec![
+        "asht asht asht",
+        "neoi neoi neoi",
+        "asht neoi asht",
+        "a s h t n e o i",
+        "ashtg yneoi",
+        "gy gy asht neoi",
+        "ht ht ne ne",
+        "sa sa oi oi",
+        "ha ta na ea",
+        "oi ie ei oe",
+        "th th th",
+        "he he he",
+        "in in in",
+        "on on on",
+        "an an an",
+        "ng ng ng",
+        "at at at",
+        "en en en",
+        "st st st",
+        "es es es",
+        "the the the",
+        "and and and",
+        "ing ing ing",
+        "one one one",
+        "hat hat hat",
+        "hang hang hang",
+        "sight sight sight",
+        "honey honey honey",
+        "thing thing thing",
+        "yes yes yes",
+        "easy tonight",
+        "the gates",
+        "a ghost sings",
+        "noisy things",
+        "a shiny hat",
+        "they see it",
+        "hang the sign",
+        "host a night",
+        "the honey is sweet",
+        "eight hats",
+        "nine toys",
+        "a tiny goat",
+        "she is hiding",
+        "saying hi",
+        "those signs",
+        "the stone gate",
+        "a shy sheep",
+        "set the tent",
+        "the night is easy tonight",
+        "they sang on the hay",
+        "a goat eats the honey",
+        "the ghost hangs on",
+        "she has eight shiny hats",
+        "the easy thing to say",
+        "nothing is shiny",
+        "ashtg ashtg yneoi",
+        "gy gy gy gy",
+        "sign sign sign",
+        "host host host",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
@@ -0,0 +1,224 @@
+use crate::keymaps::{KeyChord, parse_notation};
+use crate::vim_buffer::VimBuffer;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VimGoal {
+    Cursor(usize, usize),
+    Text(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VimChallenge {
+    pub description: &'static str,
+    pub text: &'static str,
+    pub cursor: (usize, usize),
+    pub goal: VimGoal,
+    // Shortest known answer in Vim key notation; its length is the par
+    pub solution: &'static str,
+}
+
+impl VimChallenge {
+    pub fn buffer(&self) -> VimBuffer {
+        VimBuffer::new(self.text, self.cursor.0, self.cursor.1)
+    }
+
+    pub fn solution_keys(&self) -> Vec<KeyChord> {
+        parse_notation(self.solution, &KeyChord::char('\\'))
+    }
+
+    pub fn par(&self) -> usize {
+        self.solution_keys().len()
+    }
+
+    // Only checked between commands, so a half-typed operator or an open
+    // insert never counts as solved
+    pub fn is_solved(&self, buffer: &VimBuffer) -> bool {
+        if !buffer.is_idle() {
+            return false;
+        }
+        match self.goal {
+            VimGoal::Cursor(row, col) => buffer.row == row && buffer.col == col,
+            VimGoal::Text(text) => buffer.text() == text,
+        }
+    }
+}
+
+pub fn get_vim_challenges() -> Vec<VimChallenge> {
+    vec![
+        VimChallenge {
+            description: "Move to the end of the line",
+            text: "the quick brown fox jumps",
+            cursor: (0, 0),
+            goal: VimGoal::Cursor(0, 24),
+            solution: "$",
+        },
+        VimChallenge {
+            description: "Move to the start of the third word",
+            text: "the quick brown fox jumps",
+            cursor: (0, 0),
+            goal: VimGoal::Cursor(0, 10),
+            solution: "2w",
+        },
+        VimChallenge {
+            description: "Move back two words",
+            text: "alpha beta gamma delta",
+            cursor: (0, 17),
+            goal: VimGoal::Cursor(0, 6),
+            solution: "2b",
+        },
+        VimChallenge {
+            description: "Jump to the opening parenthesis",
+            text: "let total = compute(values) + 1;",
+            cursor: (0, 0),
+            goal: VimGoal::Cursor(0, 19),
+            solution: "f(",
+        },
+        VimChallenge {
+            description: "Jump to the 'z'",
+            text: "a quick brown fox jumps over the lazy dog",
+            cursor: (0, 0),
+            goal: VimGoal::Cursor(0, 35),
+            solution: "fz",
+        },
+        VimChallenge {
+            description: "Move to the first non-blank character",
+            text: "    indented line",
+            cursor: (0, 16),
+            goal: VimGoal::Cursor(0, 4),
+            solution: "^",
+        },
+        VimChallenge {
+            description: "Go to the last line",
+            text: "fn main() {\n    let a = 1;\n    let b = 2;\n    println!(\"{}\", a + b);\n}",
+            cursor: (0, 0),
+            goal: VimGoal::Cursor(4, 0),
+            solution: "G",
+        },
+        VimChallenge {
+            description: "Go to the first line",
+            text: "first\nsecond\nthird\nfourth",
+            cursor: (3, 0),
+            goal: VimGoal::Cursor(0, 0),
+            solution: "gg",
+        },
+        VimChallenge {
+            description: "Move to the blank line after this paragraph",
+            text: "one\ntwo\nthree\n\nfour",
+            cursor: (0, 0),
+            goal: VimGoal::Cursor(3, 0),
+            solution: "}",
+        },
+        VimChallenge {
+            description: "Delete the second word",
+            text: "one two three four",
+            cursor: (0, 0),
+            goal: VimGoal::Text("one three four"),
+            solution: "wdw",
+        },
+        VimChallenge {
+            description: "Delete the first three words",
+            text: "one two three four five",
+            cursor: (0, 0),
+            goal: VimGoal::Text("four five"),
+            solution: "3dw",
+        },
+        VimChallenge {
+            description: "Delete from the comma to the end of the line",
+            text: "keep this, drop the rest",
+            cursor: (0, 9),
+            goal: VimGoal::Text("keep this"),
+            solution: "D",
+        },
+        VimChallenge {
+            description: "Delete everything up to the comma",
+            text: "first part, second part",
+            cursor: (0, 0),
+            goal: VimGoal::Text(", second part"),
+            solution: "dt,",
+        },
+        VimChallenge {
+            description: "Delete the middle line",
+            text: "keep\ndelete me\nkeep",
+            cursor: (1, 0),
+            goal: VimGoal::Text("keep\nkeep"),
+            solution: "dd",
+        },
+        VimChallenge {
+            description: "Delete the arguments inside the parentheses",
+            text: "call(a, b, c);",
+            cursor: (0, 8),
+            goal: VimGoal::Text("call();"),
+            solution: "di(",
+        },
+        VimChallenge {
+            description: "Change the word under the cursor to 'blue'",
+            text: "the red car",
+            cursor: (0, 5),
+            goal: VimGoal::Text("the blue car"),
+            solution: "ciwblue<Esc>",
+        },
+        VimChallenge {
+            description: "Replace the quoted text with 'world'",
+            text: "print(\"hello there\")",
+            cursor: (0, 0),
+            goal: VimGoal::Text("print(\"world\")"),
+            solution: "ci\"world<Esc>",
+        },
+        VimChallenge {
+            description: "Change the value after the '=' to 'done'",
+            text: "status = pending # todo",
+            cursor: (0, 9),
+            goal: VimGoal::Text("status = done"),
+            solution: "Cdone<Esc>",
+        },
+        VimChallenge {
+            description: "Replace the 5 with a 7",
+            text: "value = 5",
+            cursor: (0, 8),
+            goal: VimGoal::Text("value = 7"),
+            solution: "r7",
+        },
+        VimChallenge {
+            description: "Fix the typo by swapping two letters",
+            text: "the quikc fox",
+            cursor: (0, 0),
+            goal: VimGoal::Text("the quick fox"),
+            solution: "fkxp",
+        },
+        VimChallenge {
+            description: "Capitalize the first letter",
+            text: "hello",
+            cursor: (0, 0),
+            goal: VimGoal::Text("Hello"),
+            solution: "~",
+        },
+        VimChallenge {
+            description: "Join the two lines",
+            text: "hello\nworld",
+            cursor: (0, 0),
+            goal: VimGoal::Text("hello world"),
+            solution: "J",
+        },
+        VimChallenge {
+            description: "Duplicate the line",
+            text: "let x = 1;",
+            cursor: (0, 0),
+            goal: VimGoal::Text("let x = 1;\nlet x = 1;"),
+            solution: "yyp",
+        },
+        VimChallenge {
+            description: "Append a semicolon to the line",
+            text: "let x = 1",
+            cursor: (0, 0),
+            goal: VimGoal::Text("let x = 1;"),
+            solution: "A;<Esc>",
+        },
+        VimChallenge {
+            description: "Add a line containing 'end' below the last line",
+            text: "if ok then\n  run()",
+            cursor: (1, 2),
+            goal: VimGoal::Text("if ok then\n  run()\nend"),
+            solution: "oend<Esc>",
+        },
+    ]
+}
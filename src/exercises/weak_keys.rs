@@ -0,0 +1,103 @@
+use crate::exercises::*;
+use crate::stats::KeyStat;
+use rand::distributions::{Distribution, WeightedIndex};
+use std::collections::HashMap;
+
+const WORDS_PER_EXERCISE: usize = 6;
+
+pub fn get_weak_keys_word_pool() -> Vec<String> {
+    let mut pool: Vec<String> = Vec::new();
+    for list in [
+        get_simple_words(),
+        get_lua_words(),
+        get_ruby_words(),
+        get_rust_words(),
+        get_typescript_words(),
+        get_python_words(),
+    ] {
+        for word in list {
+            if !pool.contains(&word) {
+                pool.push(word);
+            }
+        }
+    }
+    pool
+}
+
+// Error rate dominates; keys slower than the average add a smaller boost
+fn key_weights(key_stats: &[KeyStat]) -> HashMap<char, f32> {
+    let latencies: Vec<f32> = key_stats
+        .iter()
+        .filter_map(|s| s.average_latency_ms())
+        .collect();
+    let mean_latency = if latencies.is_empty() {
+        0.0
+    } else {
+        latencies.iter().sum::<f32>() / latencies.len() as f32
+    };
+
+    key_stats
+        .iter()
+        .map(|stat| {
+            let error_weight = stat.error_rate() / 100.0 * 4.0;
+            let latency_weight = match stat.average_latency_ms() {
+                Some(latency) if mean_latency > 0.0 => (latency / mean_latency - 1.0).max(0.0),
+                _ => 0.0,
+            };
+            (stat.key, error_weight + latency_weight)
+        })
+        .collect()
+}
+
+fn word_score(
+    word: &str,
+    weights: &HashMap<char, f32>,
+    missed_bigrams: &HashMap<String, u32>,
+) -> f32 {
+    let chars: Vec<char> = word.chars().collect();
+    let key_score: f32 = chars
+        .iter()
+        .map(|c| weights.get(c).copied().unwrap_or(0.0))
+        .sum();
+    let bigram_score: f32 = chars
+        .windows(2)
+        .map(|pair| {
+            let bigram: String = pair.iter().collect();
+            missed_bigrams.get(&bigram).copied().unwrap_or(0) as f32
+        })
+        .sum();
+
+    0.1 + (key_score + bigram_score * 2.0) / (chars.len() as f32).sqrt()
+}
+
+pub fn build_weak_keys_exercise(
+    pool: &[String],
+    key_stats: &[KeyStat],
+    missed_bigrams: &HashMap<String, u32>,
+) -> String {
+    if pool.is_empty() {
+        return String::new();
+    }
+
+    let weights = key_weights(key_stats);
+    // Squaring the score sharpens the preference for words hitting weak keys
+    let scores: Vec<f32> = pool
+        .iter()
+        .map(|word| word_score(word, &weights, missed_bigrams).powi(2))
+        .collect();
+
+    let mut rng = rand::thread_rng();
+    let dist = match WeightedIndex::new(&scores) {
+        Ok(dist) => dist,
+        Err(_) => return pool[0].clone(),
+    };
+
+    let mut words: Vec<&str> = Vec::new();
+    while words.len() < WORDS_PER_EXERCISE.min(pool.len()) {
+        let word = pool[dist.sample(&mut rng)].as_str();
+        if words.last() != Some(&word) {
+            words.push(word);
+        }
+    }
+    words.join(" ")
+}
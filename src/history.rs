@@ -0,0 +1,194 @@
+use crate::stats::KeyStat;
+use crate::types::{AppMode, ExerciseCount, HistorySort, MistakePolicy};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub mode: AppMode,
+    pub layout: String,
+    pub exercise_count: String,
+    pub exercises_completed: usize,
+    pub wpm: f32,
+    #[serde(default)]
+    pub raw_wpm: f32,
+    pub accuracy: f32,
+    pub correct: u32,
+    pub errors: u32,
+    pub duration_secs: f32,
+    pub timestamp: u64,
+    #[serde(default)]
+    pub key_stats: Vec<KeyStat>,
+    #[serde(default)]
+    pub mistake_policy: MistakePolicy,
+}
+
+impl SessionRecord {
+    pub fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+
+    // UTC "YYYY-MM-DD HH:MM", avoids pulling in a date crate for one column
+    pub fn formatted_timestamp(&self) -> String {
+        let days = (self.timestamp / 86_400) as i64;
+        let secs = self.timestamp % 86_400;
+
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            year,
+            month,
+            day,
+            secs / 3_600,
+            (secs % 3_600) / 60
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionResults {
+    pub record: SessionRecord,
+    pub exercise_count: ExerciseCount,
+    pub slowest_keys: Vec<KeyStat>,
+    pub previous_best: Option<f32>,
+    pub exercises_failed: usize,
+}
+
+impl SessionResults {
+    pub fn is_new_best(&self) -> bool {
+        self.previous_best.is_none_or(|best| self.record.wpm > best)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    pub records: Vec<SessionRecord>,
+    path: Option<PathBuf>,
+}
+
+impl History {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lazydvorak").join("history.jsonl"))
+    }
+
+    pub fn load() -> Self {
+        match Self::default_path() {
+            Some(path) => Self::load_from(path),
+            None => Self::default(),
+        }
+    }
+
+    // One JSON record per line; unreadable lines are skipped so a bad write
+    // never hides the rest of the history
+    pub fn load_from(path: PathBuf) -> Self {
+        let records = fs::read_to_string(&path)
+            .map(|content| {
+                content
+                    .lines()
+                    .filter_map(|line| serde_json::from_str(line).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            records,
+            path: Some(path),
+        }
+    }
+
+    pub fn append(&mut self, record: SessionRecord) -> io::Result<()> {
+        let result = match &self.path {
+            Some(path) => Self::write_record(path, &record),
+            None => Ok(()),
+        };
+        self.records.push(record);
+        result
+    }
+
+    fn write_record(path: &PathBuf, record: &SessionRecord) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let line = serde_json::to_string(record).map_err(io::Error::other)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", line)
+    }
+
+    pub fn best_wpm(&self, mode: &AppMode, layout: &str) -> Option<f32> {
+        self.records
+            .iter()
+            .filter(|r| &r.mode == mode && r.layout == layout)
+            .map(|r| r.wpm)
+            .reduce(f32::max)
+    }
+
+    pub fn modes(&self) -> Vec<AppMode> {
+        let mut modes: Vec<AppMode> = Vec::new();
+        for record in &self.records {
+            if !modes.contains(&record.mode) {
+                modes.push(record.mode.clone());
+            }
+        }
+        modes
+    }
+
+    pub fn layouts(&self) -> Vec<String> {
+        let mut layouts: Vec<String> = Vec::new();
+        for record in &self.records {
+            if !layouts.contains(&record.layout) {
+                layouts.push(record.layout.clone());
+            }
+        }
+        layouts
+    }
+
+    pub fn query(
+        &self,
+        mode: Option<&AppMode>,
+        layout: Option<&str>,
+        sort: HistorySort,
+    ) -> Vec<&SessionRecord> {
+        let mut records: Vec<&SessionRecord> = self
+            .records
+            .iter()
+            .filter(|r| mode.is_none_or(|m| &r.mode == m))
+            .filter(|r| layout.is_none_or(|l| r.layout == l))
+            .collect();
+
+        match sort {
+            HistorySort::Newest => records.sort_by_key(|r| std::cmp::Reverse(r.timestamp)),
+            HistorySort::Oldest => records.sort_by_key(|r| r.timestamp),
+            HistorySort::Wpm => records.sort_by(|a, b| b.wpm.total_cmp(&a.wpm)),
+            HistorySort::Accuracy => records.sort_by(|a, b| b.accuracy.total_cmp(&a.accuracy)),
+        }
+        records
+    }
+
+    pub fn key_stats(&self, layout: &str) -> Vec<KeyStat> {
+        let mut merged: Vec<KeyStat> = Vec::new();
+        for record in self.records.iter().filter(|r| r.layout == layout) {
+            for stat in &record.key_stats {
+                match merged.iter_mut().find(|m| m.key == stat.key) {
+                    Some(existing) => existing.merge(stat),
+                    None => merged.push(stat.clone()),
+                }
+            }
+        }
+        merged
+    }
+}
@@ -1,250 +1,163 @@
-use crate::types::PressedKey;
+use crate::layout::{Finger, KeyboardLayout, LayoutKey};
+use crate::stats::KeyStat;
+use crate::types::{ModifierState, PressedKey};
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
+use std::collections::HashMap;
 
-pub struct KeyboardRenderer;
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeatmapMetric {
+    ErrorRate,
+    Latency,
+}
 
-impl KeyboardRenderer {
-    pub fn render_dvorak(shifted: bool, pressed_key: &Option<PressedKey>) -> Vec<Line<'static>> {
-        if shifted {
-            Self::build_shifted_dvorak(pressed_key)
-        } else {
-            Self::build_normal_dvorak(pressed_key)
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    metric: HeatmapMetric,
+    stats: HashMap<char, KeyStat>,
+    max_value: f32,
+}
+
+impl Heatmap {
+    pub fn new(metric: HeatmapMetric, key_stats: &[KeyStat]) -> Self {
+        let stats: HashMap<char, KeyStat> = key_stats.iter().map(|s| (s.key, s.clone())).collect();
+        let mut heatmap = Self {
+            metric,
+            stats,
+            max_value: 0.0,
+        };
+        heatmap.max_value = heatmap
+            .stats
+            .values()
+            .filter_map(|s| heatmap.value(s))
+            .fold(0.0, f32::max);
+        heatmap
+    }
+
+    fn value(&self, stat: &KeyStat) -> Option<f32> {
+        match self.metric {
+            HeatmapMetric::ErrorRate => (stat.attempts > 0).then(|| stat.error_rate()),
+            HeatmapMetric::Latency => stat.average_latency_ms(),
         }
     }
 
-    pub fn render_qwerty(shifted: bool, pressed_key: &Option<PressedKey>) -> Vec<Line<'static>> {
-        if shifted {
-            Self::build_shifted_qwerty(pressed_key)
+    // Keys are coloured relative to the worst key so both metrics share a scale
+    fn intensity(&self, chars: &[char]) -> Option<f32> {
+        let mut merged: Option<KeyStat> = None;
+        for c in chars {
+            if let Some(stat) = self.stats.get(c) {
+                match merged.as_mut() {
+                    Some(m) => m.merge(stat),
+                    None => merged = Some(stat.clone()),
+                }
+            }
+        }
+
+        let value = self.value(&merged?)?;
+        if self.max_value > 0.0 {
+            Some(value / self.max_value)
         } else {
-            Self::build_normal_qwerty(pressed_key)
+            Some(0.0)
         }
     }
 
-    fn build_normal_dvorak(pressed_key: &Option<PressedKey>) -> Vec<Line<'static>> {
-        vec![
-            Line::from(""),
-            Line::from(
-                "  ┌────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────────┐",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "`", "$", "&", "[", "{", "}", "(", "=", "*", ")", "+", "]", "!", "⌫",
-                ],
-                &[4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 10],
-            ),
-            Line::from(
-                "  ├────┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─────┤",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "Tab", ";", ",", ".", "p", "y", "f", "g", "c", "r", "l", "/", "@", "\\",
-                ],
-                &[7, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 5],
-            ),
-            Line::from(
-                "  ├───────┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴─────┤",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "Caps", "a", "o", "e", "u", "i", "d", "h", "t", "n", "s", "-", "Enter",
-                ],
-                &[8, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 9],
-            ),
-            Line::from(
-                "  ├────────┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴─────────┤",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "Shift", "'", "q", "j", "k", "x", "b", "m", "w", "v", "z", "Shift",
-                ],
-                &[11, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 11],
-            ),
-            Line::from(
-                "  ├──────┬────┴─┬──┴────┴────┴────┴────┴────┴────┴────┴─┬──┴──┬─┴────┬──────┤",
-            ),
-            Self::build_modifier_row(pressed_key),
-            Line::from(
-                "  └──────┴──────┴───────────────────────────────────────┴─────┴──────┴──────┘",
-            ),
-        ]
+    fn style(&self, chars: &[char]) -> Style {
+        let bg = match self.intensity(chars) {
+            None => return Style::default().fg(Color::DarkGray),
+            Some(i) if i < 0.25 => Color::Green,
+            Some(i) if i < 0.5 => Color::Yellow,
+            Some(i) if i < 0.75 => Color::LightRed,
+            Some(_) => Color::Red,
+        };
+        Style::default().fg(Color::Black).bg(bg)
     }
+}
 
-    fn build_shifted_dvorak(pressed_key: &Option<PressedKey>) -> Vec<Line<'static>> {
-        vec![
-            Line::from(""),
-            Line::from(
-                "  ┌────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────────┐",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "~", "%", "7", "5", "3", "1", "9", "0", "2", "4", "6", "8", "`", "⌫",
-                ],
-                &[4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 10],
-            ),
-            Line::from(
-                "  ├────┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─────┤",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "Tab", ":", "<", ">", "P", "Y", "F", "G", "C", "R", "L", "?", "^", "|",
-                ],
-                &[7, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 5],
-            ),
-            Line::from(
-                "  ├───────┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴─────┤",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "Caps", "A", "O", "E", "U", "I", "D", "H", "T", "N", "S", "_", "Enter",
-                ],
-                &[8, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 9],
-            ),
-            Line::from(
-                "  ├────────┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴─────────┤",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "Shift", "\"", "Q", "J", "K", "X", "B", "M", "W", "V", "Z", "Shift",
-                ],
-                &[11, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 11],
-            ),
-            Line::from(
-                "  ├──────┬────┴─┬──┴────┴────┴────┴────┴────┴────┴────┴─┬──┴──┬─┴────┬──────┤",
-            ),
-            Self::build_modifier_row(pressed_key),
-            Line::from(
-                "  └──────┴──────┴───────────────────────────────────────┴─────┴──────┴──────┘",
-            ),
-        ]
+// Zones are mirrored between hands, as on the usual touch-typing charts. Green
+// is left out so a finger zone is never mistaken for the just-pressed key
+pub fn finger_color(finger: Finger) -> Color {
+    match finger {
+        Finger::LeftPinky | Finger::RightPinky => Color::Magenta,
+        Finger::LeftRing | Finger::RightRing => Color::Blue,
+        Finger::LeftMiddle | Finger::RightMiddle => Color::Cyan,
+        Finger::LeftIndex | Finger::RightIndex => Color::Yellow,
+        Finger::LeftThumb | Finger::RightThumb => Color::Gray,
     }
+}
 
-    fn build_normal_qwerty(pressed_key: &Option<PressedKey>) -> Vec<Line<'static>> {
-        vec![
-            Line::from(""),
-            Line::from(
-                "  ┌────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────────┐",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "`", "1", "2", "3", "4", "5", "6", "7", "8", "9", "0", "-", "=", "⌫",
-                ],
-                &[4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 10],
-            ),
-            Line::from(
-                "  ├────┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─────┤",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "Tab", "q", "w", "e", "r", "t", "y", "u", "i", "o", "p", "[", "]", "\\",
-                ],
-                &[7, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 5],
-            ),
-            Line::from(
-                "  ├───────┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴─────┤",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "Caps", "a", "s", "d", "f", "g", "h", "j", "k", "l", ";", "'", "Enter",
-                ],
-                &[8, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 9],
-            ),
-            Line::from(
-                "  ├────────┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴─────────┤",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "Shift", "z", "x", "c", "v", "b", "n", "m", ",", ".", "/", "Shift",
-                ],
-                &[11, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 11],
-            ),
-            Line::from(
-                "  ├──────┬────┴─┬──┴────┴────┴────┴────┴────┴────┴────┴─┬──┴──┬─┴────┬──────┤",
-            ),
-            Self::build_modifier_row(pressed_key),
-            Line::from(
-                "  └──────┴──────┴───────────────────────────────────────┴─────┴──────┴──────┘",
-            ),
-        ]
-    }
+pub struct KeyboardRenderer;
+
+impl KeyboardRenderer {
+    pub fn render(
+        layout: &KeyboardLayout,
+        shifted: bool,
+        pressed_key: &Option<PressedKey>,
+        heatmap: Option<&Heatmap>,
+        next_key: Option<char>,
+        modifier_state: ModifierState,
+    ) -> Vec<Line<'static>> {
+        let width = layout.width();
+        let modifiers = Self::modifier_keys(width);
 
-    fn build_shifted_qwerty(pressed_key: &Option<PressedKey>) -> Vec<Line<'static>> {
-        vec![
+        // A shifted next character shows the shift layer and the Shift key of
+        // the other hand, which is the one touch typists use
+        let next_shift = next_key
+            .filter(|c| layout.needs_shift(*c))
+            .and_then(|c| layout.key_for(c))
+            .and_then(|key| key.finger)
+            .map(|finger| !finger.is_left());
+        let shifted = shifted || next_shift.is_some();
+
+        let mut boundaries: Vec<Vec<usize>> = layout
+            .rows
+            .iter()
+            .map(|row| Self::boundaries(row.keys.iter().map(|k| k.width)))
+            .collect();
+        boundaries.push(Self::boundaries(modifiers.iter().map(|(_, w)| *w)));
+
+        let mut lines = vec![
             Line::from(""),
-            Line::from(
-                "  ┌────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────┬────────┐",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "~", "!", "@", "#", "$", "%", "^", "&", "*", "(", ")", "_", "+", "⌫",
-                ],
-                &[4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 10],
-            ),
-            Line::from(
-                "  ├────┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─────┤",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "Tab", "Q", "W", "E", "R", "T", "Y", "U", "I", "O", "P", "{", "}", "|",
-                ],
-                &[7, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 5],
-            ),
-            Line::from(
-                "  ├───────┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴┬───┴─────┤",
-            ),
-            Self::build_row(
-                pressed_key,
-                vec![
-                    "Caps", "A", "S", "D", "F", "G", "H", "J", "K", "L", ":", "\"", "Enter",
-                ],
-                &[8, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 9],
-            ),
-            Line::from(
-                "  ├────────┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬─┴──┬───────────┤",
-            ),
-            Self::build_row(
+            Self::build_border(None, Some(&boundaries[0]), width),
+        ];
+
+        for (i, row) in layout.rows.iter().enumerate() {
+            lines.push(Self::build_row(
                 pressed_key,
-                vec![
-                    "Shift", "Z", "X", "C", "V", "B", "N", "M", "<", ">", "?", "Shift",
-                ],
-                &[11, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 11],
-            ),
-            Line::from(
-                "  ├──────┬────┴─┬──┴────┴────┴────┴────┴────┴────┴────┴─┬──┴──┬─┴────┬──────┤",
-            ),
-            Self::build_modifier_row(pressed_key),
-            Line::from(
-                "  └──────┴──────┴───────────────────────────────────────┴─────┴──────┴──────┘",
-            ),
-        ]
-    }
+                &row.keys,
+                shifted,
+                heatmap,
+                next_key,
+                next_shift,
+                modifier_state,
+            ));
+            lines.push(Self::build_border(
+                Some(&boundaries[i]),
+                Some(&boundaries[i + 1]),
+                width,
+            ));
+        }
 
-    fn build_modifier_row(pressed_key: &Option<PressedKey>) -> Line<'static> {
-        let mut spans = vec![Span::raw("  │")];
+        lines.push(Self::build_modifier_row(
+            pressed_key,
+            &modifiers,
+            heatmap,
+            next_key,
+            modifier_state,
+        ));
+        lines.push(Self::build_border(boundaries.last(), None, width));
+        lines
+    }
 
+    fn modifier_keys(width: usize) -> Vec<(&'static str, usize)> {
         let is_mac = cfg!(target_os = "macos");
 
-        let modifiers = if is_mac {
+        let mut modifiers = if is_mac {
             vec![
                 ("Ctrl", 6),
                 ("Opt", 6),
-                ("Space", 39),
+                ("Space", 0),
                 ("Cmd", 5),
                 ("Opt", 6),
                 ("Ctrl", 6),
@@ -253,43 +166,88 @@ impl KeyboardRenderer {
             vec![
                 ("Ctrl", 6),
                 ("Alt", 6),
-                ("Space", 39),
+                ("Space", 0),
                 ("Alt", 5),
                 ("Fn", 6),
                 ("Ctrl", 6),
             ]
         };
 
-        for (key, width) in modifiers {
-            let is_pressed = if let Some(pk) = pressed_key {
-                pk.is_active() && Self::modifier_matches(&pk.display, key)
-            } else {
-                false
-            };
+        let used: usize = modifiers.iter().map(|(_, w)| w + 1).sum();
+        modifiers[2].1 = width.saturating_sub(used).max(5);
+        modifiers
+    }
 
-            let style = if is_pressed {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Green)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
-            };
+    fn boundaries(widths: impl Iterator<Item = usize>) -> Vec<usize> {
+        let mut positions = vec![0];
+        let mut pos = 0;
+        for w in widths {
+            pos += w + 1;
+            positions.push(pos);
+        }
+        positions
+    }
 
-            let padding = if width > key.len() {
-                (width - key.len()) / 2
-            } else {
-                0
-            };
+    fn build_border(
+        upper: Option<&Vec<usize>>,
+        lower: Option<&Vec<usize>>,
+        width: usize,
+    ) -> Line<'static> {
+        let (left, right) = match (upper, lower) {
+            (None, _) => ('┌', '┐'),
+            (_, None) => ('└', '┘'),
+            _ => ('├', '┤'),
+        };
+
+        let mut border = String::from("  ");
+        border.push(left);
+        for col in 1..width {
+            let up = upper.is_some_and(|b| b.contains(&col));
+            let down = lower.is_some_and(|b| b.contains(&col));
+            border.push(match (up, down) {
+                (true, true) => '┼',
+                (true, false) => '┴',
+                (false, true) => '┬',
+                (false, false) => '─',
+            });
+        }
+        border.push(right);
+
+        Line::from(border)
+    }
 
-            let padded_key = format!(
-                "{}{}{}",
-                " ".repeat(padding),
-                key,
-                " ".repeat(width - key.len() - padding)
-            );
+    fn build_modifier_row(
+        pressed_key: &Option<PressedKey>,
+        modifiers: &[(&'static str, usize)],
+        heatmap: Option<&Heatmap>,
+        next_key: Option<char>,
+        modifier_state: ModifierState,
+    ) -> Line<'static> {
+        let mut spans = vec![Span::raw("  │")];
 
-            spans.push(Span::styled(padded_key, style));
+        // Space sits in the middle, so the index tells which side a key is on
+        for (i, (key, width)) in modifiers.iter().enumerate() {
+            let left = i < 2;
+            let is_pressed = match *key {
+                "Ctrl" if left => modifier_state.left_ctrl,
+                "Ctrl" => modifier_state.right_ctrl,
+                "Alt" | "Opt" if left => modifier_state.left_alt,
+                "Alt" | "Opt" => modifier_state.right_alt,
+                "Cmd" => modifier_state.super_key,
+                "Space" => pressed_key
+                    .as_ref()
+                    .is_some_and(|pk| pk.is_active() && pk.display == " "),
+                _ => false,
+            };
+
+            let is_space = *key == "Space";
+            let chars: &[char] = if is_space { &[' '] } else { &[] };
+            let finger = is_space.then_some(Finger::RightThumb);
+            let is_next = is_space && next_key == Some(' ');
+            spans.push(Span::styled(
+                Self::pad(key, *width),
+                Self::key_style(is_pressed, is_next, heatmap, chars, finger),
+            ));
             spans.push(Span::raw("│"));
         }
 
@@ -298,64 +256,98 @@ impl KeyboardRenderer {
 
     fn build_row(
         pressed_key: &Option<PressedKey>,
-        keys: Vec<&str>,
-        widths: &[usize],
+        keys: &[LayoutKey],
+        shifted: bool,
+        heatmap: Option<&Heatmap>,
+        next_key: Option<char>,
+        next_shift: Option<bool>,
+        modifier_state: ModifierState,
     ) -> Line<'static> {
         let mut spans = vec![Span::raw("  │")];
 
-        for (i, key) in keys.iter().enumerate() {
-            let is_pressed = if let Some(pk) = pressed_key {
-                pk.is_active() && Self::key_matches(&pk.display, key)
-            } else {
-                false
-            };
-
-            let style = if is_pressed {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Green)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
-            };
-
-            let width = widths.get(i).copied().unwrap_or(4);
-            let padding = if width > key.len() {
-                (width - key.len()) / 2
-            } else {
-                0
+        for key in keys {
+            let legend = key.legend(shifted);
+            let left = key.finger.is_none_or(|f| f.is_left());
+            let is_pressed = match legend.as_str() {
+                "Shift" if key.named && left => modifier_state.left_shift,
+                "Shift" if key.named => modifier_state.right_shift,
+                "Caps" if key.named => modifier_state.caps_lock,
+                _ => pressed_key
+                    .as_ref()
+                    .is_some_and(|pk| pk.is_active() && Self::key_matches(&pk.display, &legend)),
             };
 
-            let padded_key = format!(
-                "{}{}{}",
-                " ".repeat(padding),
-                key,
-                " ".repeat(width - key.len() - padding)
-            );
-
-            spans.push(Span::styled(padded_key, style));
+            let chars = key.chars();
+            let is_next = next_key.is_some_and(|c| chars.contains(&c))
+                || (key.named
+                    && key.base == "Shift"
+                    && next_shift.is_some()
+                    && next_shift == key.finger.map(|f| f.is_left()));
+            spans.push(Span::styled(
+                Self::pad(&legend, key.width),
+                Self::key_style(is_pressed, is_next, heatmap, &chars, key.finger),
+            ));
             spans.push(Span::raw("│"));
         }
 
         Line::from(spans)
     }
 
+    // The just-pressed key wins over the next one, which wins over the
+    // heatmap; without a heatmap legends take their finger's colour
+    fn key_style(
+        is_pressed: bool,
+        is_next: bool,
+        heatmap: Option<&Heatmap>,
+        chars: &[char],
+        finger: Option<Finger>,
+    ) -> Style {
+        if is_pressed {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Green)
+                .add_modifier(Modifier::BOLD)
+        } else if is_next {
+            Style::default()
+                .fg(Color::Black)
+                .bg(finger.map(finger_color).unwrap_or(Color::White))
+                .add_modifier(Modifier::BOLD)
+        } else if let Some(heatmap) = heatmap {
+            heatmap.style(chars)
+        } else {
+            Style::default().fg(finger.map(finger_color).unwrap_or(Color::White))
+        }
+    }
+
+    fn pad(legend: &str, width: usize) -> String {
+        let len = legend.chars().count();
+        let padding = if width > len { (width - len) / 2 } else { 0 };
+
+        format!(
+            "{}{}{}",
+            " ".repeat(padding),
+            legend,
+            " ".repeat(width.saturating_sub(len + padding))
+        )
+    }
+
     fn key_matches(pressed: &str, key: &str) -> bool {
         let pressed_lower = pressed.to_lowercase();
 
-        let special_keys = ["tab", "caps", "enter", "shift", "backspace", "⌫"];
+        let special_keys = ["tab", "enter", "backspace", "⌫"];
         let key_lower = key.to_lowercase();
 
         if special_keys.contains(&key_lower.as_str()) {
             return pressed_lower.contains(&key_lower);
         }
 
-        if pressed.contains('<') && pressed.contains('>') {
-            if let Some(char_part) = pressed.split('-').last() {
-                let clean_char = char_part.trim_end_matches('>');
-                if clean_char.len() == 1 && key.len() == 1 {
-                    return clean_char.eq_ignore_ascii_case(key);
-                }
+        if pressed.contains('<')
+            && pressed.contains('>')
+            && let Some(char_part) = pressed.split('-').next_back()
+        {
+            let clean_char = char_part.trim_end_matches('>');
+            if clean_char.len() == 1 && key.len() == 1 {
+                return clean_char.eq_ignore_ascii_case(key);
             }
         }
 
@@ -365,28 +357,4 @@ impl KeyboardRenderer {
 
         pressed == key
     }
-
-    fn modifier_matches(pressed: &str, modifier: &str) -> bool {
-        let pressed_lower = pressed.to_lowercase();
-        let modifier_lower = modifier.to_lowercase();
-
-        match modifier_lower.as_str() {
-            "ctrl" => pressed_lower.contains("ctrl") || pressed_lower.contains("<c-"),
-            "alt" | "opt" => {
-                pressed_lower.contains("alt")
-                    || pressed_lower.contains("opt")
-                    || pressed_lower.contains("<a-")
-                    || pressed_lower.contains("<m-")
-            }
-            "cmd" | "command" => {
-                pressed_lower.contains("cmd")
-                    || pressed_lower.contains("command")
-                    || pressed_lower.contains("<d-")
-            }
-            "shift" => pressed_lower.contains("shift") || pressed_lower.contains("<s-"),
-            "fn" => pressed_lower.contains("fn"),
-            "space" => pressed_lower.contains("space") || pressed == " ",
-            _ => false,
-        }
-    }
 }
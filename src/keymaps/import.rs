@@ -0,0 +1,469 @@
+use super::Keymap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const KEYMAP_FUNCTIONS: &[&str] = &["vim.keymap.set", "vim.api.nvim_set_keymap"];
+const BUFFER_KEYMAP_FUNCTIONS: &[&str] = &["vim.api.nvim_buf_set_keymap"];
+const VIM_MAP_ARGS: &[&str] = &[
+    "<buffer>",
+    "<nowait>",
+    "<silent>",
+    "<script>",
+    "<expr>",
+    "<unique>",
+    "<special>",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Str(String),
+    Ident(String),
+    Punct(char),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Str(String),
+    Table(Vec<(Option<String>, Value)>),
+    Other,
+}
+
+impl Value {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn field(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Table(fields) => fields
+                .iter()
+                .find(|(key, _)| key.as_deref() == Some(name))
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn positional(&self) -> Vec<&Value> {
+        match self {
+            Value::Table(fields) => fields
+                .iter()
+                .filter(|(key, _)| key.is_none())
+                .map(|(_, value)| value)
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    // "n" or { "n", "v" } -> "nv"
+    fn as_mode(&self) -> Option<String> {
+        match self {
+            Value::Str(s) => Some(s.clone()),
+            Value::Table(_) => {
+                let modes: Vec<&str> = self
+                    .positional()
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .collect();
+                (!modes.is_empty()).then(|| modes.concat())
+            }
+            Value::Other => None,
+        }
+    }
+}
+
+// Walks a Neovim config tree (init.lua, lua/, plugin/, after/ ...) and collects
+// every mapping it can find in .lua and .vim files
+pub fn import_nvim_config(dir: &Path) -> io::Result<Vec<Keymap>> {
+    let mut keymaps = Vec::new();
+    collect_dir(dir, &mut keymaps)?;
+    Ok(dedup(keymaps))
+}
+
+fn collect_dir(dir: &Path, keymaps: &mut Vec<Keymap>) -> io::Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.path());
+
+    for entry in entries {
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+
+        if entry.file_type()?.is_dir() {
+            collect_dir(&path, keymaps)?;
+            continue;
+        }
+
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("lua") => {
+                keymaps.extend(parse_lua(&content));
+                keymaps.extend(parse_vimscript(&content));
+            }
+            Some("vim") => keymaps.extend(parse_vimscript(&content)),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn dedup(keymaps: Vec<Keymap>) -> Vec<Keymap> {
+    let mut unique: Vec<Keymap> = Vec::new();
+    for keymap in keymaps {
+        let is_plug = keymap.lhs.to_lowercase().starts_with("<plug>");
+        let duplicate = unique
+            .iter()
+            .any(|k| k.lhs == keymap.lhs && k.mode == keymap.mode);
+        if !keymap.lhs.is_empty() && !is_plug && !duplicate {
+            unique.push(keymap);
+        }
+    }
+    unique
+}
+
+// Covers vim.keymap.set / nvim_set_keymap calls (including local aliases and
+// `local function map(mode, lhs, ...)` wrappers) and lazy.nvim `keys = {...}`
+pub fn parse_lua(content: &str) -> Vec<Keymap> {
+    let tokens = tokenize_lua(content);
+    let functions = keymap_functions(&tokens);
+
+    let mut keymaps = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if let Token::Ident(name) = &tokens[i]
+            && tokens.get(i + 1) == Some(&Token::Punct('('))
+        {
+            let skip = if functions.contains(name) {
+                Some(0)
+            } else if BUFFER_KEYMAP_FUNCTIONS.contains(&name.as_str()) {
+                Some(1)
+            } else {
+                None
+            };
+
+            if let Some(skip) = skip {
+                let (args, next) = parse_args(&tokens, i + 2);
+                if let Some(keymap) = keymap_from_call(&args[skip.min(args.len())..]) {
+                    keymaps.push(keymap);
+                }
+                i = next;
+                continue;
+            }
+        }
+
+        if tokens[i] == Token::Ident("keys".to_string())
+            && tokens.get(i + 1) == Some(&Token::Punct('='))
+            && tokens.get(i + 2) == Some(&Token::Punct('{'))
+        {
+            let (value, next) = parse_value(&tokens, i + 2);
+            keymaps.extend(keymaps_from_lazy_keys(&value));
+            i = next;
+            continue;
+        }
+
+        i += 1;
+    }
+    keymaps
+}
+
+fn keymap_functions(tokens: &[Token]) -> Vec<String> {
+    let mut functions: Vec<String> = KEYMAP_FUNCTIONS.iter().map(|f| f.to_string()).collect();
+
+    for (i, token) in tokens.iter().enumerate() {
+        // local map = vim.keymap.set
+        if let Token::Ident(name) = token
+            && tokens.get(i + 1) == Some(&Token::Punct('='))
+            && let Some(Token::Ident(target)) = tokens.get(i + 2)
+            && KEYMAP_FUNCTIONS.contains(&target.as_str())
+            && tokens.get(i + 3) != Some(&Token::Punct('('))
+        {
+            functions.push(name.clone());
+        }
+
+        // local function map(mode, lhs, rhs, opts)
+        if *token == Token::Ident("function".to_string())
+            && let Some(Token::Ident(name)) = tokens.get(i + 1)
+            && tokens.get(i + 2) == Some(&Token::Punct('('))
+            && let Some(Token::Ident(first)) = tokens.get(i + 3)
+            && tokens.get(i + 4) == Some(&Token::Punct(','))
+            && matches!(first.as_str(), "mode" | "modes" | "m")
+        {
+            functions.push(name.clone());
+        }
+    }
+    functions
+}
+
+fn keymap_from_call(args: &[Value]) -> Option<Keymap> {
+    let lhs = args.get(1)?.as_str()?;
+    let mut keymap = Keymap::new(lhs);
+    keymap.mode = args.first().and_then(|m| m.as_mode());
+    keymap.rhs = args.get(2).and_then(|r| r.as_str()).map(str::to_string);
+    keymap.desc = args
+        .get(3)
+        .and_then(|opts| opts.field("desc"))
+        .and_then(|d| d.as_str())
+        .map(str::to_string);
+    Some(keymap)
+}
+
+// keys = { "<leader>x", { "<leader>ff", "<cmd>...<cr>", desc = "...", mode = "n" } }
+fn keymaps_from_lazy_keys(keys: &Value) -> Vec<Keymap> {
+    let mut keymaps = Vec::new();
+    for spec in keys.positional() {
+        let keymap = match spec {
+            Value::Str(lhs) => {
+                let mut keymap = Keymap::new(lhs);
+                keymap.mode = Some("n".to_string());
+                keymap
+            }
+            Value::Table(_) => {
+                let positional = spec.positional();
+                let Some(lhs) = positional.first().and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let mut keymap = Keymap::new(lhs);
+                keymap.rhs = positional
+                    .get(1)
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string);
+                keymap.desc = spec
+                    .field("desc")
+                    .and_then(|d| d.as_str())
+                    .map(str::to_string);
+                keymap.mode = Some(
+                    spec.field("mode")
+                        .and_then(|m| m.as_mode())
+                        .unwrap_or_else(|| "n".to_string()),
+                );
+                keymap
+            }
+            Value::Other => continue,
+        };
+        keymaps.push(keymap);
+    }
+    keymaps
+}
+
+// Arguments up to the closing parenthesis; returns the index after it
+fn parse_args(tokens: &[Token], mut i: usize) -> (Vec<Value>, usize) {
+    let mut args = Vec::new();
+    while i < tokens.len() {
+        match &tokens[i] {
+            Token::Punct(')') => return (args, i + 1),
+            // Stray closers from malformed code are dropped
+            Token::Punct(',' | ';' | '}' | ']') => i += 1,
+            _ => {
+                let (value, next) = parse_value(tokens, i);
+                args.push(value);
+                i = next.max(i + 1);
+            }
+        }
+    }
+    (args, i)
+}
+
+// A single expression: string literals and table constructors are kept, any
+// other expression is skipped up to the next separator at the same depth
+fn parse_value(tokens: &[Token], i: usize) -> (Value, usize) {
+    match tokens.get(i) {
+        Some(Token::Str(s)) if is_separator(tokens.get(i + 1)) => (Value::Str(s.clone()), i + 1),
+        Some(Token::Punct('{')) => parse_table(tokens, i + 1),
+        _ => (Value::Other, skip_expression(tokens, i)),
+    }
+}
+
+fn parse_table(tokens: &[Token], mut i: usize) -> (Value, usize) {
+    let mut fields = Vec::new();
+    while i < tokens.len() {
+        match &tokens[i] {
+            Token::Punct('}') => return (Value::Table(fields), i + 1),
+            // An unclosed table ends with the call around it
+            Token::Punct(')') => return (Value::Table(fields), i),
+            Token::Punct(',' | ';' | ']') => i += 1,
+            Token::Ident(key) if tokens.get(i + 1) == Some(&Token::Punct('=')) => {
+                let (value, next) = parse_value(tokens, i + 2);
+                fields.push((Some(key.clone()), value));
+                i = next;
+            }
+            _ => {
+                let (value, next) = parse_value(tokens, i);
+                fields.push((None, value));
+                i = next.max(i + 1);
+            }
+        }
+    }
+    (Value::Table(fields), i)
+}
+
+fn is_separator(token: Option<&Token>) -> bool {
+    matches!(
+        token,
+        None | Some(Token::Punct(',' | ';' | ')' | '}' | ']'))
+    )
+}
+
+fn skip_expression(tokens: &[Token], mut i: usize) -> usize {
+    let mut depth = 0usize;
+    let mut block_depth = 0usize;
+    while i < tokens.len() {
+        match &tokens[i] {
+            Token::Punct('(' | '{' | '[') => depth += 1,
+            Token::Punct(')' | '}' | ']') if depth == 0 && block_depth == 0 => return i,
+            Token::Punct(')' | '}' | ']') => depth = depth.saturating_sub(1),
+            Token::Punct(',' | ';') if depth == 0 && block_depth == 0 => return i,
+            // Inline `function() ... end` bodies contain their own commas
+            Token::Ident(word) if matches!(word.as_str(), "function" | "do" | "then") => {
+                block_depth += 1
+            }
+            Token::Ident(word) if matches!(word.as_str(), "end" | "elseif") => {
+                block_depth = block_depth.saturating_sub(1)
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    i
+}
+
+fn tokenize_lua(content: &str) -> Vec<Token> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '-' && chars.get(i + 1) == Some(&'-') {
+            i += 2;
+            if let Some((_, next)) = long_bracket(&chars, i) {
+                i = next;
+            } else {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+        } else if c == '"' || c == '\'' {
+            let (text, next) = quoted_string(&chars, i + 1, c);
+            tokens.push(Token::Str(text));
+            i = next;
+        } else if c == '['
+            && let Some((text, next)) = long_bracket(&chars, i)
+        {
+            tokens.push(Token::Str(text));
+            i = next;
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else {
+            tokens.push(Token::Punct(c));
+            i += 1;
+        }
+    }
+    tokens
+}
+
+fn quoted_string(chars: &[char], mut i: usize, quote: char) -> (String, usize) {
+    let mut text = String::new();
+    while i < chars.len() && chars[i] != quote && chars[i] != '\n' {
+        if chars[i] == '\\' && i + 1 < chars.len() {
+            text.push(match chars[i + 1] {
+                'n' => '\n',
+                't' => '\t',
+                other => other,
+            });
+            i += 2;
+        } else {
+            text.push(chars[i]);
+            i += 1;
+        }
+    }
+    (text, i + 1)
+}
+
+// [[...]], [==[...]==] and the same after `--` for block comments
+fn long_bracket(chars: &[char], i: usize) -> Option<(String, usize)> {
+    if chars.get(i) != Some(&'[') {
+        return None;
+    }
+    let mut level = 0;
+    while chars.get(i + 1 + level) == Some(&'=') {
+        level += 1;
+    }
+    if chars.get(i + 1 + level) != Some(&'[') {
+        return None;
+    }
+
+    let mut closing = vec![']'];
+    closing.extend(std::iter::repeat_n('=', level));
+    closing.push(']');
+
+    let start = i + 2 + level;
+    let mut j = start;
+    while j < chars.len() {
+        if chars[j..].starts_with(&closing) {
+            return Some((chars[start..j].iter().collect(), j + closing.len()));
+        }
+        j += 1;
+    }
+    Some((chars[start..].iter().collect(), chars.len()))
+}
+
+// :map, nnoremap, vmap!, xnoremap <silent> <leader>x :cmd<CR>, ...
+pub fn parse_vimscript(content: &str) -> Vec<Keymap> {
+    content.lines().filter_map(parse_vim_map_line).collect()
+}
+
+fn parse_vim_map_line(line: &str) -> Option<Keymap> {
+    let line = line.trim_start().trim_start_matches(':');
+    let mut parts = line.splitn(2, char::is_whitespace);
+    let command = parts.next()?;
+    let mut rest = parts.next()?.trim_start();
+    if rest.starts_with(['=', '(']) {
+        return None;
+    }
+
+    let (command, bang) = match command.strip_suffix('!') {
+        Some(command) => (command, true),
+        None => (command, false),
+    };
+    let base = command
+        .strip_suffix("noremap")
+        .or_else(|| command.strip_suffix("map"))?;
+    let mode = match base {
+        "" if bang => Some("!".to_string()),
+        "" => None,
+        _ if bang => return None,
+        "n" | "v" | "x" | "s" | "o" | "i" | "l" | "c" | "t" => Some(base.to_string()),
+        _ => return None,
+    };
+
+    loop {
+        let lower = rest.to_lowercase();
+        match VIM_MAP_ARGS.iter().find(|arg| lower.starts_with(*arg)) {
+            Some(arg) => rest = rest[arg.len()..].trim_start(),
+            None => break,
+        }
+    }
+
+    let mut parts = rest.splitn(2, char::is_whitespace);
+    let lhs = parts.next().filter(|lhs| !lhs.is_empty())?;
+    let rhs = parts.next().map(str::trim).filter(|rhs| !rhs.is_empty());
+
+    let mut keymap = Keymap::new(lhs);
+    keymap.mode = mode;
+    keymap.rhs = rhs.map(str::to_string);
+    Some(keymap)
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub mod import;
+pub mod notation;
+
+pub use import::import_nvim_config;
+pub use notation::{KeyChord, parse_leader, parse_notation};
+
+#[derive(Debug)]
+pub enum KeymapError {
+    Io(String, std::io::Error),
+    Parse(String, String),
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeymapError::Io(source, err) => write!(f, "cannot read keymaps {}: {}", source, err),
+            KeymapError::Parse(source, err) => {
+                write!(f, "cannot parse keymaps {}: {}", source, err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+// Field names follow the dictionaries returned by nvim_get_keymap(), so a
+// dump of that output can be loaded as-is; unknown fields are ignored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keymap {
+    pub lhs: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(
+        default,
+        alias = "description",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub desc: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rhs: Option<String>,
+}
+
+impl Keymap {
+    pub fn new(lhs: &str) -> Self {
+        Self {
+            lhs: lhs.to_string(),
+            mode: None,
+            desc: None,
+            rhs: None,
+        }
+    }
+
+    fn with(mut self, mode: &str, desc: &str, rhs: &str) -> Self {
+        self.mode = Some(mode.to_string());
+        self.desc = Some(desc.to_string());
+        self.rhs = Some(rhs.to_string());
+        self
+    }
+}
+
+// A keymaps file is a JSON array whose entries are either a bare lhs string
+// (the original format) or a full mapping object; both can be mixed
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeymapEntry {
+    Lhs(String),
+    Full(Keymap),
+}
+
+pub fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("lazydvorak").join("keymaps.json"))
+}
+
+pub fn load_keymaps(path: &Path) -> Result<Vec<Keymap>, KeymapError> {
+    let source = path.display().to_string();
+    let content = std::fs::read_to_string(path).map_err(|e| KeymapError::Io(source.clone(), e))?;
+    parse_keymaps(&content).map_err(|e| KeymapError::Parse(source, e))
+}
+
+pub fn parse_keymaps(content: &str) -> Result<Vec<Keymap>, String> {
+    let entries: Vec<KeymapEntry> = serde_json::from_str(content).map_err(|e| e.to_string())?;
+
+    let mut keymaps: Vec<Keymap> = Vec::new();
+    for entry in entries {
+        let keymap = match entry {
+            KeymapEntry::Lhs(lhs) => Keymap::new(&lhs),
+            KeymapEntry::Full(keymap) => keymap,
+        };
+        let duplicate = keymaps
+            .iter()
+            .any(|k| k.lhs == keymap.lhs && k.mode == keymap.mode);
+        if !keymap.lhs.trim().is_empty() && !duplicate {
+            keymaps.push(keymap);
+        }
+    }
+    Ok(keymaps)
+}
+
+pub fn default_keymaps() -> Vec<Keymap> {
+    vec![
+        Keymap::new("<leader>ff").with("n", "Find files", "<cmd>Telescope find_files<CR>"),
+        Keymap::new("<C-n>").with("n", "Toggle file tree", "<cmd>NvimTreeToggle<CR>"),
+        Keymap::new("<Space>b").with("n", "List buffers", "<cmd>Telescope buffers<CR>"),
+        Keymap::new("<C-w>v").with("n", "Split window vertically", "<C-w>v"),
+        Keymap::new("<A-j>").with("n", "Move line down", "<cmd>m .+1<CR>=="),
+    ]
+}
@@ -0,0 +1,193 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    pub fn char(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    // Terminals report Ctrl/Alt letters in lower case and fold Shift into the
+    // character itself, so Shift is only compared for non-character keys
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let relevant = KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER;
+        if self.modifiers & relevant != key.modifiers & relevant {
+            return false;
+        }
+
+        match (self.code, key.code) {
+            (KeyCode::Char(expected), KeyCode::Char(typed)) => {
+                if self.modifiers.intersects(relevant) {
+                    expected.eq_ignore_ascii_case(&typed)
+                } else {
+                    expected == typed
+                }
+            }
+            (KeyCode::BackTab, KeyCode::Tab) => key.modifiers.contains(KeyModifiers::SHIFT),
+            (expected, typed) => {
+                expected == typed
+                    && (self.modifiers.contains(KeyModifiers::SHIFT)
+                        == key.modifiers.contains(KeyModifiers::SHIFT)
+                        || expected == KeyCode::BackTab)
+            }
+        }
+    }
+
+    // Character used to attribute the chord in per-key statistics
+    pub fn base_char(&self) -> Option<char> {
+        match self.code {
+            KeyCode::Char(c) => Some(c),
+            KeyCode::Enter => Some('\n'),
+            KeyCode::Tab | KeyCode::BackTab => Some('\t'),
+            _ => None,
+        }
+    }
+
+    pub fn display(&self) -> String {
+        let name = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char('<') => "lt".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "CR".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "S-Tab".to_string(),
+            KeyCode::Backspace => "BS".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Insert => "Insert".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PageUp".to_string(),
+            KeyCode::PageDown => "PageDown".to_string(),
+            KeyCode::Up => "Up".to_string(),
+            KeyCode::Down => "Down".to_string(),
+            KeyCode::Left => "Left".to_string(),
+            KeyCode::Right => "Right".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            _ => "?".to_string(),
+        };
+
+        let mut prefix = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            prefix.push_str("C-");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            prefix.push_str("A-");
+        }
+        if self.modifiers.contains(KeyModifiers::SUPER) {
+            prefix.push_str("D-");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            prefix.push_str("S-");
+        }
+
+        match self.code {
+            KeyCode::Char(c) if prefix.is_empty() && c != ' ' && c != '<' => name,
+            _ => format!("<{}{}>", prefix, name),
+        }
+    }
+}
+
+// Parses Vim key notation ("<leader>ff", "<C-w>v", "<A-j>", "gcc") into the
+// chords that have to be pressed; unknown <...> groups are typed literally
+pub fn parse_notation(lhs: &str, leader: &KeyChord) -> Vec<KeyChord> {
+    let chars: Vec<char> = lhs.chars().collect();
+    let mut chords = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] == '<'
+            && let Some(len) = chars[i + 1..].iter().position(|&c| c == '>')
+            && len > 0
+        {
+            let name: String = chars[i + 1..i + 1 + len].iter().collect();
+            if let Some(chord) = parse_special(&name, leader) {
+                chords.push(chord);
+                i += len + 2;
+                continue;
+            }
+        }
+        chords.push(KeyChord::char(chars[i]));
+        i += 1;
+    }
+    chords
+}
+
+// The leader is written either in notation ("<Space>", "\\") or as the raw key
+pub fn parse_leader(spec: &str) -> Option<KeyChord> {
+    if spec == " " {
+        return Some(KeyChord::char(' '));
+    }
+    let fallback = KeyChord::char('\\');
+    match parse_notation(spec, &fallback).as_slice() {
+        [chord] => Some(chord.clone()),
+        _ => None,
+    }
+}
+
+fn parse_special(name: &str, leader: &KeyChord) -> Option<KeyChord> {
+    let lower = name.to_lowercase();
+    if lower == "leader" || lower == "localleader" {
+        return Some(leader.clone());
+    }
+
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = name;
+    while rest.len() > 2 && rest.as_bytes()[1] == b'-' {
+        modifiers |= match rest.as_bytes()[0].to_ascii_lowercase() {
+            b'c' => KeyModifiers::CONTROL,
+            b'a' | b'm' => KeyModifiers::ALT,
+            b's' => KeyModifiers::SHIFT,
+            b'd' => KeyModifiers::SUPER,
+            _ => return None,
+        };
+        rest = &rest[2..];
+    }
+
+    let code = match rest.to_lowercase().as_str() {
+        "space" => KeyCode::Char(' '),
+        "cr" | "enter" | "return" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+            modifiers.remove(KeyModifiers::SHIFT);
+            KeyCode::BackTab
+        }
+        "tab" => KeyCode::Tab,
+        "bs" | "backspace" => KeyCode::Backspace,
+        "del" | "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "lt" => KeyCode::Char('<'),
+        "bar" => KeyCode::Char('|'),
+        "bslash" => KeyCode::Char('\\'),
+        key if key.len() > 1 && key.starts_with('f') => KeyCode::F(key[1..].parse().ok()?),
+        _ => {
+            let mut chars = rest.chars();
+            let c = chars.next()?;
+            if chars.next().is_some() || modifiers.is_empty() {
+                return None;
+            }
+            // <S-a> is just "A"
+            if modifiers == KeyModifiers::SHIFT && c.is_alphabetic() {
+                return Some(KeyChord::char(c.to_ascii_uppercase()));
+            }
+            KeyCode::Char(c)
+        }
+    };
+    Some(KeyChord::new(code, modifiers))
+}
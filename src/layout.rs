@@ -0,0 +1,303 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+const BUILTIN_LAYOUTS: &[&str] = &[
+    include_str!("layouts/dvorak.toml"),
+    include_str!("layouts/qwerty.toml"),
+    include_str!("layouts/dvorak-ansi.toml"),
+    include_str!("layouts/colemak.toml"),
+    include_str!("layouts/colemak-dh.toml"),
+    include_str!("layouts/workman.toml"),
+    include_str!("layouts/norman.toml"),
+];
+
+#[derive(Debug)]
+pub enum LayoutError {
+    Io(String, std::io::Error),
+    Parse(String, String),
+    Invalid(String, String),
+    Unknown(String),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::Io(source, err) => write!(f, "cannot read layout {}: {}", source, err),
+            LayoutError::Parse(source, err) => write!(f, "cannot parse layout {}: {}", source, err),
+            LayoutError::Invalid(source, reason) => {
+                write!(f, "invalid layout {}: {}", source, reason)
+            }
+            LayoutError::Unknown(name) => write!(
+                f,
+                "unknown layout '{}' (built-in layouts: {})",
+                name,
+                KeyboardLayout::builtin_ids().join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Finger {
+    LeftPinky,
+    LeftRing,
+    LeftMiddle,
+    LeftIndex,
+    LeftThumb,
+    RightThumb,
+    RightIndex,
+    RightMiddle,
+    RightRing,
+    RightPinky,
+}
+
+impl Finger {
+    pub fn is_left(&self) -> bool {
+        matches!(
+            self,
+            Finger::LeftPinky
+                | Finger::LeftRing
+                | Finger::LeftMiddle
+                | Finger::LeftIndex
+                | Finger::LeftThumb
+        )
+    }
+
+    pub fn display(&self) -> &str {
+        match self {
+            Finger::LeftPinky => "Left pinky",
+            Finger::LeftRing => "Left ring",
+            Finger::LeftMiddle => "Left middle",
+            Finger::LeftIndex => "Left index",
+            Finger::LeftThumb => "Left thumb",
+            Finger::RightThumb => "Right thumb",
+            Finger::RightIndex => "Right index",
+            Finger::RightMiddle => "Right middle",
+            Finger::RightRing => "Right ring",
+            Finger::RightPinky => "Right pinky",
+        }
+    }
+}
+
+fn default_key_width() -> usize {
+    4
+}
+
+fn default_home_row() -> usize {
+    2
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutKey {
+    pub base: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shift: Option<String>,
+    #[serde(default = "default_key_width")]
+    pub width: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finger: Option<Finger>,
+    #[serde(default)]
+    pub named: bool,
+}
+
+impl LayoutKey {
+    // Characters this key types on its base and shift layers
+    pub fn chars(&self) -> Vec<char> {
+        if self.named {
+            return match self.base.as_str() {
+                "Tab" => vec!['\t'],
+                "Enter" => vec!['\n'],
+                _ => vec![],
+            };
+        }
+
+        let mut chars = Vec::new();
+        for legend in [self.base.clone(), self.legend(true)] {
+            let mut it = legend.chars();
+            if let (Some(c), None) = (it.next(), it.next())
+                && !chars.contains(&c)
+            {
+                chars.push(c);
+            }
+        }
+        chars
+    }
+
+    pub fn legend(&self, shifted: bool) -> String {
+        if !shifted || self.named {
+            return self.base.clone();
+        }
+        match &self.shift {
+            Some(shift) => shift.clone(),
+            None => self.base.to_uppercase(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutRow {
+    pub keys: Vec<LayoutKey>,
+}
+
+impl LayoutRow {
+    pub fn width(&self) -> usize {
+        self.keys.iter().map(|k| k.width + 1).sum::<usize>()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyboardLayout {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_home_row")]
+    pub home_row: usize,
+    pub rows: Vec<LayoutRow>,
+}
+
+impl KeyboardLayout {
+    pub fn builtin() -> Vec<KeyboardLayout> {
+        BUILTIN_LAYOUTS
+            .iter()
+            .map(|src| Self::from_toml_str(src, "<built-in>").expect("built-in layouts are valid"))
+            .collect()
+    }
+
+    pub fn builtin_ids() -> Vec<String> {
+        Self::builtin().into_iter().map(|l| l.id).collect()
+    }
+
+    pub fn find_builtin(id: &str) -> Option<KeyboardLayout> {
+        let id = id.to_lowercase();
+        Self::builtin().into_iter().find(|l| l.id == id)
+    }
+
+    // Accepts either a built-in layout id or a path to a .toml/.json layout file
+    pub fn resolve(spec: &str) -> Result<KeyboardLayout, LayoutError> {
+        if let Some(layout) = Self::find_builtin(spec) {
+            return Ok(layout);
+        }
+        let path = Path::new(spec);
+        if path.is_file() {
+            return Self::from_file(path);
+        }
+        Err(LayoutError::Unknown(spec.to_string()))
+    }
+
+    pub fn from_file(path: &Path) -> Result<KeyboardLayout, LayoutError> {
+        let source = path.display().to_string();
+        let content =
+            std::fs::read_to_string(path).map_err(|e| LayoutError::Io(source.clone(), e))?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json_str(&content, &source),
+            _ => Self::from_toml_str(&content, &source),
+        }
+    }
+
+    pub fn from_toml_str(content: &str, source: &str) -> Result<KeyboardLayout, LayoutError> {
+        let layout: KeyboardLayout = toml::from_str(content)
+            .map_err(|e| LayoutError::Parse(source.to_string(), e.to_string()))?;
+        layout.validate(source)?;
+        Ok(layout)
+    }
+
+    pub fn from_json_str(content: &str, source: &str) -> Result<KeyboardLayout, LayoutError> {
+        let layout: KeyboardLayout = serde_json::from_str(content)
+            .map_err(|e| LayoutError::Parse(source.to_string(), e.to_string()))?;
+        layout.validate(source)?;
+        Ok(layout)
+    }
+
+    fn validate(&self, source: &str) -> Result<(), LayoutError> {
+        let invalid = |reason: String| LayoutError::Invalid(source.to_string(), reason);
+
+        if self.rows.is_empty() {
+            return Err(invalid("layout has no rows".to_string()));
+        }
+        if self.home_row >= self.rows.len() {
+            return Err(invalid(format!(
+                "home_row {} is out of range",
+                self.home_row
+            )));
+        }
+
+        let width = self.width();
+        for (i, row) in self.rows.iter().enumerate() {
+            if row.keys.is_empty() {
+                return Err(invalid(format!("row {} has no keys", i + 1)));
+            }
+            if row.width() != width {
+                return Err(invalid(format!(
+                    "row {} is {} columns wide, expected {}",
+                    i + 1,
+                    row.width(),
+                    width
+                )));
+            }
+            if let Some(key) = row.keys.iter().find(|k| k.width == 0) {
+                return Err(invalid(format!("key '{}' has zero width", key.base)));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.first().map(|r| r.width()).unwrap_or(0)
+    }
+
+    pub fn key_for(&self, c: char) -> Option<&LayoutKey> {
+        self.rows
+            .iter()
+            .flat_map(|row| row.keys.iter())
+            .find(|key| key.chars().contains(&c))
+    }
+
+    // True when `c` only exists on the shift layer of its key
+    pub fn needs_shift(&self, c: char) -> bool {
+        self.key_for(c)
+            .is_some_and(|key| !key.named && key.base != c.to_string())
+    }
+
+    // Emulation pairs keys by row and position, which only lines up when both
+    // layouts have as many keys on every row
+    pub fn same_geometry(&self, other: &KeyboardLayout) -> bool {
+        self.rows.len() == other.rows.len()
+            && self
+                .rows
+                .iter()
+                .zip(&other.rows)
+                .all(|(a, b)| a.keys.len() == b.keys.len())
+    }
+
+    // Character this layout produces on the physical key (and shift layer)
+    // where `from` has `c`, so a system set to `from` can emulate this layout
+    pub fn remap_from(&self, from: &KeyboardLayout, c: char) -> Option<char> {
+        for (row, from_row) in from.rows.iter().enumerate() {
+            for (index, from_key) in from_row.keys.iter().enumerate() {
+                if from_key.named {
+                    continue;
+                }
+                for shifted in [false, true] {
+                    if from_key.legend(shifted) != c.to_string() {
+                        continue;
+                    }
+                    let key = self.rows.get(row)?.keys.get(index)?;
+                    if key.named {
+                        return None;
+                    }
+                    let legend = key.legend(shifted);
+                    let mut chars = legend.chars();
+                    return match (chars.next(), chars.next()) {
+                        (Some(mapped), None) => Some(mapped),
+                        _ => None,
+                    };
+                }
+            }
+        }
+        None
+    }
+}
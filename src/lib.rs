@@ -1,7 +1,14 @@
+pub mod analysis;
 pub mod app;
 pub mod exercises;
+pub mod history;
 pub mod keyboard;
+pub mod keymaps;
+pub mod layout;
+pub mod romaji;
 pub mod stats;
+pub mod text;
 pub mod translations;
 pub mod types;
 pub mod ui;
+pub mod vim_buffer;
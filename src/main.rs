@@ -1,12 +1,27 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use crossterm::{
-    event::{self, Event},
+    event::{
+        self, Event, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+        supports_keyboard_enhancement,
+    },
+};
+use lazydvorak::{
+    analysis::{analyze, format_report, read_corpus},
+    app::App,
+    exercises::{SplitMode, load_exercises_from_dir, load_exercises_from_file},
+    keymaps::parse_leader,
+    layout::KeyboardLayout,
+    ui,
 };
-use lazydvorak::{app::App, ui};
 use ratatui::{Terminal, backend::CrosstermBackend};
 use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -14,22 +29,152 @@ struct Args {
     #[arg(short, long, default_value = "en")]
     lang: String,
 
+    /// Built-in layout id or path to a .toml/.json layout file
     #[arg(short = 'k', long, default_value = "dvorak")]
     layout: String,
+
+    /// Text or source file to load as Custom File exercises (repeatable)
+    #[arg(long)]
+    file: Vec<PathBuf>,
+
+    /// Directory to load Custom File exercises from, recursively (repeatable)
+    #[arg(long)]
+    dir: Vec<PathBuf>,
+
+    /// How to split custom files into exercises: auto, lines, blocks or file
+    #[arg(long, default_value = "auto")]
+    split: String,
+
+    /// JSON file with the Neovim keymaps to practice [default: <config dir>/lazydvorak/keymaps.json]
+    #[arg(long)]
+    keymaps: Option<PathBuf>,
+
+    /// Import keymaps from a Neovim config directory [default: <config dir>/nvim]
+    #[arg(long, value_name = "DIR")]
+    nvim_config: Option<Option<PathBuf>>,
+
+    /// Key that <leader> stands for in keymap exercises, e.g. "<Space>" or ","
+    #[arg(long, default_value = "\\")]
+    leader: String,
+
+    /// Layout the system is set to; keys are translated to --layout on the fly
+    #[arg(long, value_name = "LAYOUT")]
+    emulate: Option<String>,
+
+    /// Milliseconds between UI refreshes when no key is pressed
+    #[arg(long, default_value_t = 50)]
+    tick_rate: u64,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Report finger load, hand alternation, same-finger bigrams, row jumps
+    /// and home row usage of a layout for a text corpus
+    Analyze {
+        /// Layout to analyze; repeat to compare layouts side by side
+        #[arg(short = 'k', long = "layout", default_value = "dvorak")]
+        layouts: Vec<String>,
+
+        /// Files or directories (read recursively) making up the corpus
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
 }
 
 fn main() -> Result<(), io::Error> {
     let args = Args::parse();
 
+    if let Some(Command::Analyze { layouts, paths }) = &args.command {
+        if let Err(err) = run_analyze(layouts, paths) {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+        return Ok(());
+    }
+
+    let layout = match KeyboardLayout::resolve(&args.layout) {
+        Ok(layout) => layout,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    };
+
+    let emulation_source = match args.emulate.as_deref().map(KeyboardLayout::resolve) {
+        Some(Ok(source)) if !layout.same_geometry(&source) => {
+            eprintln!(
+                "cannot emulate {} from {}: their rows have different numbers of keys",
+                layout.name, source.name
+            );
+            std::process::exit(2);
+        }
+        Some(Ok(source)) => Some(source),
+        Some(Err(err)) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+        None => None,
+    };
+
+    let custom_exercises = match load_custom_exercises(&args) {
+        Ok(exercises) => exercises,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    };
+
+    let Some(leader) = parse_leader(&args.leader) else {
+        eprintln!("invalid leader key '{}'", args.leader);
+        std::process::exit(2);
+    };
+
+    let mut app = App::new(args.lang.clone(), layout);
+    app.keymap_leader = leader;
+    app.emulation_source = emulation_source;
+    app.custom_exercises = custom_exercises;
+    let nvim_config = args
+        .nvim_config
+        .clone()
+        .and_then(|dir| dir.or_else(|| dirs::config_dir().map(|d| d.join("nvim"))));
+    if let Err(err) = app.load_nvim_keymaps(args.keymaps.as_deref(), nvim_config.as_deref()) {
+        eprintln!("{}", err);
+        std::process::exit(2);
+    }
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+
+    // Terminals speaking the kitty keyboard protocol report modifier keys on
+    // their own, releases and Caps Lock, so Shift and Caps can be shown as held
+    app.keyboard_enhanced = matches!(supports_keyboard_enhancement(), Ok(true));
+    if app.keyboard_enhanced {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                    | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+                    | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS
+                    | KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES
+            )
+        )?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new(args.lang, args.layout);
-    let res = run_app(&mut terminal, &mut app);
+    let res = run_app(
+        &mut terminal,
+        &mut app,
+        Duration::from_millis(args.tick_rate.max(1)),
+    );
 
+    if app.keyboard_enhanced {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
@@ -41,17 +186,76 @@ fn main() -> Result<(), io::Error> {
     Ok(())
 }
 
+fn load_custom_exercises(args: &Args) -> Result<Vec<String>, String> {
+    let split = SplitMode::parse(&args.split).ok_or_else(|| {
+        format!(
+            "unknown split mode '{}' (expected auto, lines, blocks or file)",
+            args.split
+        )
+    })?;
+
+    let mut exercises = Vec::new();
+    for path in &args.file {
+        let loaded = load_exercises_from_file(path, split)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        exercises.extend(loaded);
+    }
+    for path in &args.dir {
+        let loaded = load_exercises_from_dir(path, split)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        exercises.extend(loaded);
+    }
+    Ok(exercises)
+}
+
+fn run_analyze(layouts: &[String], paths: &[PathBuf]) -> Result<(), String> {
+    let layouts = layouts
+        .iter()
+        .map(|spec| KeyboardLayout::resolve(spec).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let corpus = read_corpus(paths).map_err(|e| format!("cannot read corpus: {}", e))?;
+    if !corpus.skipped.is_empty() {
+        eprintln!("Skipped {} entries:", corpus.skipped.len());
+        for (path, reason) in &corpus.skipped {
+            eprintln!("  {} ({})", path.display(), reason);
+        }
+    }
+    if corpus.texts.iter().all(|text| text.trim().is_empty()) {
+        return Err("no text to analyze".to_string());
+    }
+
+    let results: Vec<_> = layouts
+        .iter()
+        .map(|layout| analyze(layout, &corpus.texts))
+        .collect();
+    println!("{}", format_report(&results));
+    Ok(())
+}
+
 fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    tick_rate: Duration,
 ) -> io::Result<()> {
+    let mut last_tick = Instant::now();
+
     loop {
         terminal.draw(|f| ui::render(f, app))?;
 
-        if let Event::Key(key) = event::read()? {
-            if !app.handle_key(key) {
-                return Ok(());
-            }
+        // Wait for input only until the next tick is due, so typing never
+        // delays timers, WPM or key highlight updates
+        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+        if event::poll(timeout)?
+            && let Event::Key(key) = event::read()?
+            && !app.handle_key(key)
+        {
+            return Ok(());
+        }
+
+        if last_tick.elapsed() >= tick_rate {
+            app.on_tick();
+            last_tick = Instant::now();
         }
     }
 }
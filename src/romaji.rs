@@ -0,0 +1,266 @@
+// Incremental romaji input in the style of a Japanese IME: the reading is
+// matched one kana unit at a time and every accepted spelling of a unit
+// (shi/si, tsu/tu, n/nn, kka for っか...) is allowed
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RomajiStep {
+    // The key was accepted; `converted` kana of the reading are now done and
+    // `pending` holds the romaji typed towards the next unit
+    Accepted { converted: usize, pending: String },
+    // No spelling of the next unit continues with this key
+    Rejected { expected: Option<char> },
+}
+
+const TABLE: &[(&str, &[&str])] = &[
+    ("あ", &["a"]),
+    ("い", &["i", "yi"]),
+    ("う", &["u", "wu", "whu"]),
+    ("え", &["e"]),
+    ("お", &["o"]),
+    ("か", &["ka", "ca"]),
+    ("き", &["ki"]),
+    ("く", &["ku", "cu", "qu"]),
+    ("け", &["ke"]),
+    ("こ", &["ko", "co"]),
+    ("さ", &["sa"]),
+    ("し", &["shi", "si", "ci"]),
+    ("す", &["su"]),
+    ("せ", &["se", "ce"]),
+    ("そ", &["so"]),
+    ("た", &["ta"]),
+    ("ち", &["chi", "ti"]),
+    ("つ", &["tsu", "tu"]),
+    ("て", &["te"]),
+    ("と", &["to"]),
+    ("な", &["na"]),
+    ("に", &["ni"]),
+    ("ぬ", &["nu"]),
+    ("ね", &["ne"]),
+    ("の", &["no"]),
+    ("は", &["ha"]),
+    ("ひ", &["hi"]),
+    ("ふ", &["fu", "hu"]),
+    ("へ", &["he"]),
+    ("ほ", &["ho"]),
+    ("ま", &["ma"]),
+    ("み", &["mi"]),
+    ("む", &["mu"]),
+    ("め", &["me"]),
+    ("も", &["mo"]),
+    ("や", &["ya"]),
+    ("ゆ", &["yu"]),
+    ("よ", &["yo"]),
+    ("ら", &["ra"]),
+    ("り", &["ri"]),
+    ("る", &["ru"]),
+    ("れ", &["re"]),
+    ("ろ", &["ro"]),
+    ("わ", &["wa"]),
+    ("を", &["wo"]),
+    ("が", &["ga"]),
+    ("ぎ", &["gi"]),
+    ("ぐ", &["gu"]),
+    ("げ", &["ge"]),
+    ("ご", &["go"]),
+    ("ざ", &["za"]),
+    ("じ", &["ji", "zi"]),
+    ("ず", &["zu"]),
+    ("ぜ", &["ze"]),
+    ("ぞ", &["zo"]),
+    ("だ", &["da"]),
+    ("ぢ", &["di"]),
+    ("づ", &["du"]),
+    ("で", &["de"]),
+    ("ど", &["do"]),
+    ("ば", &["ba"]),
+    ("び", &["bi"]),
+    ("ぶ", &["bu"]),
+    ("べ", &["be"]),
+    ("ぼ", &["bo"]),
+    ("ぱ", &["pa"]),
+    ("ぴ", &["pi"]),
+    ("ぷ", &["pu"]),
+    ("ぺ", &["pe"]),
+    ("ぽ", &["po"]),
+    ("ぁ", &["xa", "la"]),
+    ("ぃ", &["xi", "li"]),
+    ("ぅ", &["xu", "lu"]),
+    ("ぇ", &["xe", "le"]),
+    ("ぉ", &["xo", "lo"]),
+    ("ゃ", &["xya", "lya"]),
+    ("ゅ", &["xyu", "lyu"]),
+    ("ょ", &["xyo", "lyo"]),
+    ("ゎ", &["xwa", "lwa"]),
+    ("っ", &["xtu", "ltu", "xtsu", "ltsu"]),
+    ("ん", &["nn", "xn"]),
+    ("きゃ", &["kya"]),
+    ("きゅ", &["kyu"]),
+    ("きょ", &["kyo"]),
+    ("しゃ", &["sha", "sya"]),
+    ("しゅ", &["shu", "syu"]),
+    ("しぇ", &["she", "sye"]),
+    ("しょ", &["sho", "syo"]),
+    ("ちゃ", &["cha", "tya", "cya"]),
+    ("ちゅ", &["chu", "tyu", "cyu"]),
+    ("ちぇ", &["che", "tye", "cye"]),
+    ("ちょ", &["cho", "tyo", "cyo"]),
+    ("にゃ", &["nya"]),
+    ("にゅ", &["nyu"]),
+    ("にょ", &["nyo"]),
+    ("ひゃ", &["hya"]),
+    ("ひゅ", &["hyu"]),
+    ("ひょ", &["hyo"]),
+    ("みゃ", &["mya"]),
+    ("みゅ", &["myu"]),
+    ("みょ", &["myo"]),
+    ("りゃ", &["rya"]),
+    ("りゅ", &["ryu"]),
+    ("りょ", &["ryo"]),
+    ("ぎゃ", &["gya"]),
+    ("ぎゅ", &["gyu"]),
+    ("ぎょ", &["gyo"]),
+    ("じゃ", &["ja", "zya", "jya"]),
+    ("じゅ", &["ju", "zyu", "jyu"]),
+    ("じぇ", &["je", "zye", "jye"]),
+    ("じょ", &["jo", "zyo", "jyo"]),
+    ("びゃ", &["bya"]),
+    ("びゅ", &["byu"]),
+    ("びょ", &["byo"]),
+    ("ぴゃ", &["pya"]),
+    ("ぴゅ", &["pyu"]),
+    ("ぴょ", &["pyo"]),
+    ("ふぁ", &["fa"]),
+    ("ふぃ", &["fi"]),
+    ("ふぇ", &["fe"]),
+    ("ふぉ", &["fo"]),
+    ("てぃ", &["thi"]),
+    ("でぃ", &["dhi"]),
+    ("ー", &["-"]),
+    ("。", &["."]),
+    ("、", &[","]),
+    ("・", &["/"]),
+    ("？", &["?"]),
+    ("！", &["!"]),
+    ("「", &["["]),
+    ("」", &["]"]),
+    ("　", &[" "]),
+];
+
+// Katakana is typed exactly like hiragana
+fn to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+fn lookup(kana: &[char]) -> Option<&'static [&'static str]> {
+    let kana: String = kana.iter().map(|c| to_hiragana(*c)).collect();
+    TABLE
+        .iter()
+        .find(|(k, _)| *k == kana)
+        .map(|(_, romaji)| *romaji)
+}
+
+// Every spelling of the unit at the start of `rest`, with how many kana it
+// covers
+pub fn candidates(rest: &[char]) -> Vec<(String, usize)> {
+    let Some(&first) = rest.first() else {
+        return Vec::new();
+    };
+    let mut found: Vec<(String, usize)> = Vec::new();
+
+    if rest.len() >= 2
+        && let Some(romaji) = lookup(&rest[..2])
+    {
+        found.extend(romaji.iter().map(|r| (r.to_string(), 2)));
+    }
+    if let Some(romaji) = lookup(&rest[..1]) {
+        found.extend(romaji.iter().map(|r| (r.to_string(), 1)));
+    }
+
+    match to_hiragana(first) {
+        // っ is usually typed by doubling the next consonant, so that spelling
+        // goes first and is the one suggested
+        'っ' => {
+            let mut doubled: Vec<(String, usize)> = Vec::new();
+            for (next, _) in candidates(&rest[1..]) {
+                if let Some(c) = next.chars().next()
+                    && !"aiueon".contains(c)
+                    && c.is_ascii_alphabetic()
+                    && !doubled.iter().any(|(r, _)| *r == c.to_string())
+                {
+                    doubled.push((c.to_string(), 1));
+                }
+            }
+            found.splice(0..0, doubled);
+        }
+        // A single n is only unambiguous before a consonant other than n/y
+        'ん' => {
+            let next = candidates(&rest[1..]);
+            if !next.is_empty()
+                && next
+                    .iter()
+                    .all(|(r, _)| !r.starts_with(['a', 'i', 'u', 'e', 'o', 'y', 'n']))
+            {
+                found.push(("n".to_string(), 1));
+            }
+        }
+        _ => {}
+    }
+
+    // Anything outside the table (latin letters, digits) is typed as is
+    if found.is_empty() {
+        found.push((first.to_string(), 1));
+    }
+    found
+}
+
+pub fn feed(rest: &[char], pending: &str, c: char) -> RomajiStep {
+    let input = format!("{}{}", pending, c);
+    let options = candidates(rest);
+
+    // Keep waiting while a longer spelling is still possible, like "n" before
+    // "nn" or "t" before "tsu"
+    if options
+        .iter()
+        .any(|(r, _)| r.len() > input.len() && r.starts_with(&input))
+    {
+        return RomajiStep::Accepted {
+            converted: 0,
+            pending: input,
+        };
+    }
+    if let Some((_, len)) = options.iter().find(|(r, _)| *r == input) {
+        return RomajiStep::Accepted {
+            converted: *len,
+            pending: String::new(),
+        };
+    }
+
+    // The pending romaji was already a complete unit and this key starts the
+    // next one
+    if !pending.is_empty()
+        && let Some((_, len)) = options.iter().find(|(r, _)| r == pending)
+        && let RomajiStep::Accepted { converted, pending } = feed(&rest[*len..], "", c)
+    {
+        return RomajiStep::Accepted {
+            converted: len + converted,
+            pending,
+        };
+    }
+
+    RomajiStep::Rejected {
+        expected: hint(rest, pending).and_then(|h| h.chars().nth(pending.chars().count())),
+    }
+}
+
+// Suggested spelling for the next unit, continuing what is already pending
+pub fn hint(rest: &[char], pending: &str) -> Option<String> {
+    let options = candidates(rest);
+    options
+        .iter()
+        .find(|(r, _)| r.starts_with(pending))
+        .or(options.first())
+        .map(|(r, _)| r.clone())
+}
@@ -1,4 +1,58 @@
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeystrokeRecord {
+    pub expected: char,
+    pub typed: char,
+    pub latency_ms: Option<u32>,
+    pub error: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyStat {
+    pub key: char,
+    pub attempts: u32,
+    pub errors: u32,
+    pub total_latency_ms: u64,
+    pub timed_attempts: u32,
+}
+
+impl KeyStat {
+    pub fn new(key: char) -> Self {
+        Self {
+            key,
+            attempts: 0,
+            errors: 0,
+            total_latency_ms: 0,
+            timed_attempts: 0,
+        }
+    }
+
+    pub fn error_rate(&self) -> f32 {
+        if self.attempts > 0 {
+            (self.errors as f32 / self.attempts as f32) * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    pub fn average_latency_ms(&self) -> Option<f32> {
+        if self.timed_attempts > 0 {
+            Some(self.total_latency_ms as f32 / self.timed_attempts as f32)
+        } else {
+            None
+        }
+    }
+
+    pub fn merge(&mut self, other: &KeyStat) {
+        self.attempts += other.attempts;
+        self.errors += other.errors;
+        self.total_latency_ms += other.total_latency_ms;
+        self.timed_attempts += other.timed_attempts;
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Stats {
@@ -6,7 +60,16 @@ pub struct Stats {
     pub errors: u32,
     pub total_chars: u32,
     pub start_time: Option<Instant>,
+    pub end_time: Option<Instant>,
     pub completed_exercises: u32,
+    pub keystrokes: Vec<KeystrokeRecord>,
+    pub last_keystroke: Option<Instant>,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Stats {
@@ -16,7 +79,10 @@ impl Stats {
             errors: 0,
             total_chars: 0,
             start_time: None,
+            end_time: None,
             completed_exercises: 0,
+            keystrokes: Vec::new(),
+            last_keystroke: None,
         }
     }
 
@@ -25,23 +91,145 @@ impl Stats {
         self.errors = 0;
         self.total_chars = 0;
         self.start_time = None;
+        self.end_time = None;
         self.completed_exercises = 0;
+        self.keystrokes.clear();
+        self.last_keystroke = None;
+    }
+
+    pub fn record_keystroke(&mut self, expected: char, typed: char) {
+        self.record_key(expected, typed, expected != typed);
+    }
+
+    // For keys where a matching character is not enough, e.g. chords whose
+    // modifiers also have to match
+    pub fn record_key(&mut self, expected: char, typed: char, error: bool) {
+        let now = Instant::now();
+        let latency_ms = self
+            .last_keystroke
+            .map(|last| now.duration_since(last).as_millis() as u32);
+        self.last_keystroke = Some(now);
+
+        self.keystrokes.push(KeystrokeRecord {
+            expected,
+            typed,
+            latency_ms,
+            error,
+        });
+    }
+
+    // Aggregated by the key the user was supposed to press
+    pub fn key_stats(&self) -> Vec<KeyStat> {
+        let mut by_key: HashMap<char, KeyStat> = HashMap::new();
+        for stroke in &self.keystrokes {
+            let stat = by_key
+                .entry(stroke.expected)
+                .or_insert_with(|| KeyStat::new(stroke.expected));
+            stat.attempts += 1;
+            if stroke.error {
+                stat.errors += 1;
+            }
+            if let Some(latency) = stroke.latency_ms {
+                stat.total_latency_ms += latency as u64;
+                stat.timed_attempts += 1;
+            }
+        }
+
+        let mut stats: Vec<KeyStat> = by_key.into_values().collect();
+        stats.sort_by_key(|s| s.key);
+        stats
+    }
+
+    // A bigram counts as missed when its second key was mistyped right after
+    // the first one was typed correctly
+    pub fn missed_bigrams(&self) -> HashMap<String, u32> {
+        let mut missed: HashMap<String, u32> = HashMap::new();
+        let mut previous: Option<char> = None;
+        for stroke in &self.keystrokes {
+            if stroke.error {
+                if let Some(prev) = previous {
+                    *missed
+                        .entry(format!("{}{}", prev, stroke.expected))
+                        .or_insert(0) += 1;
+                }
+            } else {
+                previous = Some(stroke.expected);
+            }
+        }
+        missed
+    }
+
+    pub fn slowest_keys(&self, count: usize) -> Vec<KeyStat> {
+        let mut stats: Vec<KeyStat> = self
+            .key_stats()
+            .into_iter()
+            .filter(|s| s.average_latency_ms().is_some())
+            .collect();
+        stats.sort_by(|a, b| {
+            let a_latency = a.average_latency_ms().unwrap_or(0.0);
+            let b_latency = b.average_latency_ms().unwrap_or(0.0);
+            b_latency.total_cmp(&a_latency)
+        });
+        stats.truncate(count);
+        stats
+    }
+
+    pub fn weakest_keys(&self, count: usize) -> Vec<KeyStat> {
+        let mut stats = self.key_stats();
+        stats.sort_by(|a, b| {
+            b.error_rate().total_cmp(&a.error_rate()).then_with(|| {
+                let a_latency = a.average_latency_ms().unwrap_or(0.0);
+                let b_latency = b.average_latency_ms().unwrap_or(0.0);
+                b_latency.total_cmp(&a_latency)
+            })
+        });
+        stats.truncate(count);
+        stats
     }
 
     // TODO!: Calculate WPM
     // WPM = (correct_chars / 5) / (time_in_minutes)
     // This assumes an average word length of 5 characters
     pub fn calculate_wpm(&self) -> f32 {
-        if let Some(start) = self.start_time {
-            let elapsed_minutes = start.elapsed().as_secs_f32() / 60.0;
-            if elapsed_minutes > 0.0 {
-                let words = (self.correct as f32) / 5.0;
-                return words / elapsed_minutes;
-            }
+        let elapsed_minutes = self.elapsed_secs() / 60.0;
+        if elapsed_minutes > 0.0 {
+            let words = (self.correct as f32) / 5.0;
+            return words / elapsed_minutes;
         }
         0.0
     }
 
+    // Counts every keystroke, including mistakes
+    pub fn calculate_raw_wpm(&self) -> f32 {
+        let elapsed_minutes = self.elapsed_secs() / 60.0;
+        if elapsed_minutes > 0.0 {
+            let words = ((self.correct + self.errors) as f32) / 5.0;
+            return words / elapsed_minutes;
+        }
+        0.0
+    }
+
+    // Stops the clock so WPM and duration stay fixed after the session ends
+    pub fn finish(&mut self, at: Instant) {
+        if self.start_time.is_some() && self.end_time.is_none() {
+            self.end_time = Some(at);
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        match self.start_time {
+            Some(start) => self
+                .end_time
+                .unwrap_or_else(Instant::now)
+                .duration_since(start),
+            None => Duration::ZERO,
+        }
+    }
+
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed().as_secs_f32()
+    }
+
     pub fn calculate_accuracy(&self) -> f32 {
         let total_attempts = self.correct + self.errors;
         if total_attempts > 0 {
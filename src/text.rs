@@ -0,0 +1,30 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+// Exercises are tracked in grapheme clusters rather than bytes or chars, so an
+// accented letter, a CJK character or an emoji is always a single position
+
+pub fn graphemes(text: &str) -> Vec<&str> {
+    text.graphemes(true).collect()
+}
+
+pub fn grapheme_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+pub fn grapheme_at(text: &str, index: usize) -> Option<&str> {
+    text.graphemes(true).nth(index)
+}
+
+pub fn pop_grapheme(text: &mut String) -> Option<String> {
+    let (start, last) = text.grapheme_indices(true).next_back()?;
+    let last = last.to_string();
+    text.truncate(start);
+    Some(last)
+}
+
+// Terminal cells taken by a grapheme: 2 for wide CJK characters and most
+// emoji, and never less than 1 so control characters keep a visible slot
+pub fn display_width(grapheme: &str) -> usize {
+    grapheme.width().max(1)
+}
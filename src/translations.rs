@@ -1,3 +1,6 @@
+use crate::layout::Finger;
+use crate::types::{AppMode, MistakePolicy};
+
 pub struct Translations {
     pub main_menu: String,
     pub settings: String,
@@ -5,12 +8,48 @@ pub struct Translations {
     pub simple_words: String,
     pub vim_commands: String,
     pub words_by_language: String,
+    pub weak_keys_drill: String,
     pub sentence_practice: String,
     pub sentences_normal: String,
     pub sentences_dvorak: String,
     pub sentences_qwerty: String,
+    pub sentences_colemak: String,
+    pub sentences_colemak_dh: String,
+    pub sentences_workman: String,
+    pub sentences_norman: String,
+    pub sentences_japanese: String,
+    pub reading: String,
+    pub romaji: String,
     pub real_code_test: String,
     pub custom_keymaps: String,
+    pub skip_indentation: String,
+    pub layout_emulation: String,
+    pub next_key: String,
+    pub finger_pinky: String,
+    pub finger_ring: String,
+    pub finger_middle: String,
+    pub finger_index: String,
+    pub finger_thumb: String,
+    pub left_hand: String,
+    pub right_hand: String,
+    pub on: String,
+    pub off: String,
+    pub mistake_policy: String,
+    pub policy_block: String,
+    pub policy_free: String,
+    pub policy_sudden_death: String,
+    pub ended_on_error: String,
+    pub vim_motions: String,
+    pub goal: String,
+    pub expected: String,
+    pub keystrokes: String,
+    pub par: String,
+    pub solved_in: String,
+    pub nav_vim_motions: String,
+    pub description: String,
+    pub maps_to: String,
+    pub custom_file: String,
+    pub no_custom_exercises: String,
     pub about: String,
     pub exit: String,
     pub select_language_label: String,
@@ -32,6 +71,7 @@ pub struct Translations {
     pub shift: String,
     pub ctrl: String,
     pub alt: String,
+    pub caps_lock: String,
     pub space: String,
     pub target: String,
     pub typed: String,
@@ -46,6 +86,31 @@ pub struct Translations {
     pub basic: String,
     pub intermediate: String,
     pub advanced: String,
+    pub history: String,
+    pub sort: String,
+    pub mode: String,
+    pub all: String,
+    pub sessions: String,
+    pub best: String,
+    pub date: String,
+    pub duration: String,
+    pub no_history: String,
+    pub nav_history: String,
+    pub newest: String,
+    pub oldest: String,
+    pub weak_keys: String,
+    pub time_left: String,
+    pub results: String,
+    pub raw_wpm: String,
+    pub slowest_keys: String,
+    pub previous_best: String,
+    pub new_best: String,
+    pub retry: String,
+    pub back_to_menu: String,
+    pub heatmap_session_errors: String,
+    pub heatmap_session_latency: String,
+    pub heatmap_history_errors: String,
+    pub heatmap_history_latency: String,
 }
 
 impl Translations {
@@ -58,12 +123,48 @@ impl Translations {
                 simple_words: "Palabras Simples".to_string(),
                 vim_commands: "Comandos Vim".to_string(),
                 words_by_language: "Palabras por Lenguaje".to_string(),
+                weak_keys_drill: "Teclas Débiles".to_string(),
                 sentence_practice: "Oraciones (Intermedio)".to_string(),
                 sentences_normal: "Oraciones Normales".to_string(),
                 sentences_dvorak: "Oraciones Dvorak".to_string(),
                 sentences_qwerty: "Oraciones Qwerty".to_string(),
+                sentences_colemak: "Oraciones Colemak".to_string(),
+                sentences_colemak_dh: "Oraciones Colemak-DH".to_string(),
+                sentences_workman: "Oraciones Workman".to_string(),
+                sentences_norman: "Oraciones Norman".to_string(),
+                sentences_japanese: "Japonés (romaji → kana)".to_string(),
+                reading: "Lectura".to_string(),
+                romaji: "Romaji".to_string(),
                 real_code_test: "Test de Código Real (Avanzado)".to_string(),
                 custom_keymaps: "Keymaps Personalizados".to_string(),
+                skip_indentation: "Saltar sangría tras Enter".to_string(),
+                layout_emulation: "Emular desde".to_string(),
+                next_key: "Siguiente tecla".to_string(),
+                finger_pinky: "Meñique".to_string(),
+                finger_ring: "Anular".to_string(),
+                finger_middle: "Medio".to_string(),
+                finger_index: "Índice".to_string(),
+                finger_thumb: "Pulgar".to_string(),
+                left_hand: "izquierda".to_string(),
+                right_hand: "derecha".to_string(),
+                on: "Sí".to_string(),
+                off: "No".to_string(),
+                mistake_policy: "Política de errores".to_string(),
+                policy_block: "Bloquear en error".to_string(),
+                policy_free: "Escritura libre".to_string(),
+                policy_sudden_death: "Muerte súbita".to_string(),
+                ended_on_error: "terminaron en un error".to_string(),
+                vim_motions: "Movimientos de Vim".to_string(),
+                goal: "Objetivo".to_string(),
+                expected: "Esperado".to_string(),
+                keystrokes: "Pulsaciones".to_string(),
+                par: "Par".to_string(),
+                solved_in: "Resuelto en".to_string(),
+                nav_vim_motions: "Escribe comandos de Vim | Esc (modo normal): Menú | F5: Reiniciar | F2: Mapa de calor".to_string(),
+                description: "Descripción".to_string(),
+                maps_to: "Ejecuta".to_string(),
+                custom_file: "Archivo Personalizado".to_string(),
+                no_custom_exercises: "No hay ejercicios personalizados. Inicia con --file <ruta> o --dir <ruta>.".to_string(),
                 about: "Acerca de".to_string(),
                 exit: "Salir".to_string(),
                 select_language_label: "Selecciona Lenguaje".to_string(),
@@ -85,12 +186,13 @@ impl Translations {
                 shift: "MAYÚS".to_string(),
                 ctrl: "CTRL".to_string(),
                 alt: "ALT".to_string(),
+                caps_lock: "BLOQ MAYÚS".to_string(),
                 space: "ESPACIO".to_string(),
                 target: "Objetivo".to_string(),
                 typed: "Escrito".to_string(),
                 nav_menu: "↑/↓: Navegar | Enter: Seleccionar | q: Salir".to_string(),
                 nav_submenu: "↑/↓: Navegar | Enter: Seleccionar | Esc: Volver".to_string(),
-                nav_practice: "Escribe para practicar | Esc: Menú | Backspace: Borrar".to_string(),
+                nav_practice: "Escribe para practicar | Esc: Menú | Backspace: Borrar | F2: Mapa de calor".to_string(),
                 keyboard_layout: "Distribución del Teclado".to_string(),
                 language: "Idioma".to_string(),
                 github: "GitHub".to_string(),
@@ -99,6 +201,31 @@ impl Translations {
                 basic: "Básico".to_string(),
                 intermediate: "Intermedio".to_string(),
                 advanced: "Avanzado".to_string(),
+                history: "Historial".to_string(),
+                sort: "Orden".to_string(),
+                mode: "Modo".to_string(),
+                all: "Todos".to_string(),
+                sessions: "Sesiones".to_string(),
+                best: "Mejor".to_string(),
+                date: "Fecha".to_string(),
+                duration: "Duración".to_string(),
+                no_history: "Aún no hay sesiones registradas".to_string(),
+                nav_history: "↑/↓: Desplazar | s: Ordenar | m: Modo | l: Distribución | Esc: Volver".to_string(),
+                newest: "Más recientes".to_string(),
+                oldest: "Más antiguas".to_string(),
+                weak_keys: "Teclas débiles".to_string(),
+                time_left: "Tiempo restante".to_string(),
+                results: "Resultados".to_string(),
+                raw_wpm: "PPM bruto".to_string(),
+                slowest_keys: "Teclas más lentas".to_string(),
+                previous_best: "Mejor anterior".to_string(),
+                new_best: "¡Nuevo récord!".to_string(),
+                retry: "Reintentar".to_string(),
+                back_to_menu: "Volver al menú".to_string(),
+                heatmap_session_errors: "Mapa de calor: errores de la sesión".to_string(),
+                heatmap_session_latency: "Mapa de calor: latencia de la sesión".to_string(),
+                heatmap_history_errors: "Mapa de calor: errores del historial".to_string(),
+                heatmap_history_latency: "Mapa de calor: latencia del historial".to_string(),
             },
             "ja" => Self {
                 main_menu: "メインメニュー".to_string(),
@@ -107,12 +234,48 @@ impl Translations {
                 simple_words: "簡単な単語".to_string(),
                 vim_commands: "Vimコマンド".to_string(),
                 words_by_language: "言語別単語".to_string(),
+                weak_keys_drill: "苦手キー練習".to_string(),
                 sentence_practice: "文(中級)".to_string(),
                 sentences_normal: "通常の文".to_string(),
                 sentences_dvorak: "Dvorakの文".to_string(),
                 sentences_qwerty: "Qwertyの文".to_string(),
+                sentences_colemak: "Colemakの文".to_string(),
+                sentences_colemak_dh: "Colemak-DHの文".to_string(),
+                sentences_workman: "Workmanの文".to_string(),
+                sentences_norman: "Normanの文".to_string(),
+                sentences_japanese: "日本語（ローマ字入力）".to_string(),
+                reading: "読み".to_string(),
+                romaji: "ローマ字".to_string(),
                 real_code_test: "実際のコードテスト(上級)".to_string(),
                 custom_keymaps: "カスタムキーマップ".to_string(),
+                skip_indentation: "Enter後のインデントを自動入力".to_string(),
+                layout_emulation: "配列エミュレーション".to_string(),
+                next_key: "次のキー".to_string(),
+                finger_pinky: "小指".to_string(),
+                finger_ring: "薬指".to_string(),
+                finger_middle: "中指".to_string(),
+                finger_index: "人差し指".to_string(),
+                finger_thumb: "親指".to_string(),
+                left_hand: "左手".to_string(),
+                right_hand: "右手".to_string(),
+                on: "オン".to_string(),
+                off: "オフ".to_string(),
+                mistake_policy: "ミス時の動作".to_string(),
+                policy_block: "ミスで停止".to_string(),
+                policy_free: "自由入力".to_string(),
+                policy_sudden_death: "サドンデス".to_string(),
+                ended_on_error: "件がミスで終了".to_string(),
+                vim_motions: "Vimモーション".to_string(),
+                goal: "目標".to_string(),
+                expected: "期待される結果".to_string(),
+                keystrokes: "キー入力".to_string(),
+                par: "パー".to_string(),
+                solved_in: "解決キー数".to_string(),
+                nav_vim_motions: "Vimコマンドを入力 | Esc (ノーマルモード): メニュー | F5: やり直し | F2: ヒートマップ".to_string(),
+                description: "説明".to_string(),
+                maps_to: "実行内容".to_string(),
+                custom_file: "カスタムファイル".to_string(),
+                no_custom_exercises: "カスタム練習が読み込まれていません。--file <パス> または --dir <パス> で起動してください。".to_string(),
                 about: "について".to_string(),
                 exit: "終了".to_string(),
                 select_language_label: "言語を選択".to_string(),
@@ -134,12 +297,13 @@ impl Translations {
                 shift: "SHIFT".to_string(),
                 ctrl: "CTRL".to_string(),
                 alt: "ALT".to_string(),
+                caps_lock: "CAPS LOCK".to_string(),
                 space: "スペース".to_string(),
                 target: "目標".to_string(),
                 typed: "入力済み".to_string(),
                 nav_menu: "↑/↓: 移動 | Enter: 選択 | q: 終了".to_string(),
                 nav_submenu: "↑/↓: 移動 | Enter: 選択 | Esc: 戻る".to_string(),
-                nav_practice: "練習のために入力 | Esc: メニュー | Backspace: 削除".to_string(),
+                nav_practice: "練習のために入力 | Esc: メニュー | Backspace: 削除 | F2: ヒートマップ".to_string(),
                 keyboard_layout: "キーボードレイアウト".to_string(),
                 language: "言語".to_string(),
                 github: "GitHub".to_string(),
@@ -148,6 +312,31 @@ impl Translations {
                 basic: "基本".to_string(),
                 intermediate: "中級".to_string(),
                 advanced: "上級".to_string(),
+                history: "履歴".to_string(),
+                sort: "並び順".to_string(),
+                mode: "モード".to_string(),
+                all: "すべて".to_string(),
+                sessions: "セッション".to_string(),
+                best: "最高".to_string(),
+                date: "日付".to_string(),
+                duration: "時間".to_string(),
+                no_history: "記録されたセッションはまだありません".to_string(),
+                nav_history: "↑/↓: スクロール | s: 並び替え | m: モード | l: レイアウト | Esc: 戻る".to_string(),
+                newest: "新しい順".to_string(),
+                oldest: "古い順".to_string(),
+                weak_keys: "苦手なキー".to_string(),
+                time_left: "残り時間".to_string(),
+                results: "結果".to_string(),
+                raw_wpm: "生WPM".to_string(),
+                slowest_keys: "遅いキー".to_string(),
+                previous_best: "前回の最高".to_string(),
+                new_best: "新記録!".to_string(),
+                retry: "再挑戦".to_string(),
+                back_to_menu: "メニューに戻る".to_string(),
+                heatmap_session_errors: "ヒートマップ: セッションのエラー".to_string(),
+                heatmap_session_latency: "ヒートマップ: セッションの遅延".to_string(),
+                heatmap_history_errors: "ヒートマップ: 履歴のエラー".to_string(),
+                heatmap_history_latency: "ヒートマップ: 履歴の遅延".to_string(),
             },
             _ => Self {
                 main_menu: "Main Menu".to_string(),
@@ -156,12 +345,48 @@ impl Translations {
                 simple_words: "Simple Words".to_string(),
                 vim_commands: "Vim Commands".to_string(),
                 words_by_language: "Words by Language".to_string(),
+                weak_keys_drill: "Weak Keys".to_string(),
                 sentence_practice: "Sentences (Intermediate)".to_string(),
                 sentences_normal: "Normal Sentences".to_string(),
                 sentences_dvorak: "Dvorak Sentences".to_string(),
                 sentences_qwerty: "Qwerty Sentences".to_string(),
+                sentences_colemak: "Colemak Sentences".to_string(),
+                sentences_colemak_dh: "Colemak-DH Sentences".to_string(),
+                sentences_workman: "Workman Sentences".to_string(),
+                sentences_norman: "Norman Sentences".to_string(),
+                sentences_japanese: "Japanese (romaji → kana)".to_string(),
+                reading: "Reading".to_string(),
+                romaji: "Romaji".to_string(),
                 real_code_test: "Real Code Test (Advanced)".to_string(),
                 custom_keymaps: "Custom Keymaps".to_string(),
+                skip_indentation: "Skip indentation after Enter".to_string(),
+                layout_emulation: "Emulate from".to_string(),
+                next_key: "Next key".to_string(),
+                finger_pinky: "Pinky".to_string(),
+                finger_ring: "Ring".to_string(),
+                finger_middle: "Middle".to_string(),
+                finger_index: "Index".to_string(),
+                finger_thumb: "Thumb".to_string(),
+                left_hand: "left".to_string(),
+                right_hand: "right".to_string(),
+                on: "On".to_string(),
+                off: "Off".to_string(),
+                mistake_policy: "Mistake policy".to_string(),
+                policy_block: "Block on error".to_string(),
+                policy_free: "Free typing".to_string(),
+                policy_sudden_death: "Sudden death".to_string(),
+                ended_on_error: "ended on an error".to_string(),
+                vim_motions: "Vim Motions".to_string(),
+                goal: "Goal".to_string(),
+                expected: "Expected".to_string(),
+                keystrokes: "Keystrokes".to_string(),
+                par: "Par".to_string(),
+                solved_in: "Solved in".to_string(),
+                nav_vim_motions: "Type Vim commands | Esc (normal mode): Menu | F5: Restart | F2: Heatmap".to_string(),
+                description: "Description".to_string(),
+                maps_to: "Maps to".to_string(),
+                custom_file: "Custom File".to_string(),
+                no_custom_exercises: "No custom exercises loaded. Start with --file <path> or --dir <path>.".to_string(),
                 about: "About".to_string(),
                 exit: "Exit".to_string(),
                 select_language_label: "Select Language".to_string(),
@@ -183,12 +408,13 @@ impl Translations {
                 shift: "SHIFT".to_string(),
                 ctrl: "CTRL".to_string(),
                 alt: "ALT".to_string(),
+                caps_lock: "CAPS LOCK".to_string(),
                 space: "SPACE".to_string(),
                 target: "Target".to_string(),
                 typed: "Typed".to_string(),
                 nav_menu: "↑/↓: Navigate | Enter: Select | q: Quit".to_string(),
                 nav_submenu: "↑/↓: Navigate | Enter: Select | Esc: Back".to_string(),
-                nav_practice: "Type to practice | Esc: Menu | Backspace: Delete".to_string(),
+                nav_practice: "Type to practice | Esc: Menu | Backspace: Delete | F2: Heatmap".to_string(),
                 keyboard_layout: "Keyboard Layout".to_string(),
                 language: "Language".to_string(),
                 github: "GitHub".to_string(),
@@ -197,7 +423,88 @@ impl Translations {
                 basic: "Basic".to_string(),
                 intermediate: "Intermediate".to_string(),
                 advanced: "Advanced".to_string(),
+                history: "History".to_string(),
+                sort: "Sort".to_string(),
+                mode: "Mode".to_string(),
+                all: "All".to_string(),
+                sessions: "Sessions".to_string(),
+                best: "Best".to_string(),
+                date: "Date".to_string(),
+                duration: "Duration".to_string(),
+                no_history: "No sessions recorded yet".to_string(),
+                nav_history: "↑/↓: Scroll | s: Sort | m: Mode | l: Layout | Esc: Back".to_string(),
+                newest: "Newest".to_string(),
+                oldest: "Oldest".to_string(),
+                weak_keys: "Weak keys".to_string(),
+                time_left: "Time left".to_string(),
+                results: "Results".to_string(),
+                raw_wpm: "Raw WPM".to_string(),
+                slowest_keys: "Slowest keys".to_string(),
+                previous_best: "Previous best".to_string(),
+                new_best: "New best!".to_string(),
+                retry: "Retry".to_string(),
+                back_to_menu: "Back to menu".to_string(),
+                heatmap_session_errors: "Heatmap: session errors".to_string(),
+                heatmap_session_latency: "Heatmap: session latency".to_string(),
+                heatmap_history_errors: "Heatmap: history errors".to_string(),
+                heatmap_history_latency: "Heatmap: history latency".to_string(),
             },
         }
     }
+
+    pub fn policy_name(&self, policy: MistakePolicy) -> &str {
+        match policy {
+            MistakePolicy::Block => &self.policy_block,
+            MistakePolicy::Free => &self.policy_free,
+            MistakePolicy::SuddenDeath => &self.policy_sudden_death,
+        }
+    }
+
+    pub fn finger_name(&self, finger: Finger) -> String {
+        let name = match finger {
+            Finger::LeftPinky | Finger::RightPinky => &self.finger_pinky,
+            Finger::LeftRing | Finger::RightRing => &self.finger_ring,
+            Finger::LeftMiddle | Finger::RightMiddle => &self.finger_middle,
+            Finger::LeftIndex | Finger::RightIndex => &self.finger_index,
+            Finger::LeftThumb | Finger::RightThumb => &self.finger_thumb,
+        };
+        let hand = if finger.is_left() {
+            &self.left_hand
+        } else {
+            &self.right_hand
+        };
+        format!("{} ({})", name, hand)
+    }
+
+    pub fn mode_name(&self, mode: &AppMode) -> String {
+        match mode {
+            AppMode::VimCommands => self.vim_commands.clone(),
+            AppMode::WordsSimple => self.simple_words.clone(),
+            AppMode::WordsWeakKeys => self.weak_keys_drill.clone(),
+            AppMode::WordsLua => format!("{} · {}", self.words_by_language, self.lua),
+            AppMode::WordsRuby => format!("{} · {}", self.words_by_language, self.ruby),
+            AppMode::WordsRust => format!("{} · {}", self.words_by_language, self.rust),
+            AppMode::WordsTypescript => {
+                format!("{} · {}", self.words_by_language, self.typescript)
+            }
+            AppMode::WordsPython => format!("{} · {}", self.words_by_language, self.python),
+            AppMode::SentencesNormal => self.sentences_normal.clone(),
+            AppMode::SentencesDvorak => self.sentences_dvorak.clone(),
+            AppMode::SentencesQwerty => self.sentences_qwerty.clone(),
+            AppMode::SentencesColemak => self.sentences_colemak.clone(),
+            AppMode::SentencesColemakDh => self.sentences_colemak_dh.clone(),
+            AppMode::SentencesWorkman => self.sentences_workman.clone(),
+            AppMode::SentencesNorman => self.sentences_norman.clone(),
+            AppMode::SentencesJapanese => self.sentences_japanese.clone(),
+            AppMode::CodeTestLua => format!("{} · {}", self.advanced, self.lua),
+            AppMode::CodeTestRuby => format!("{} · {}", self.advanced, self.ruby),
+            AppMode::CodeTestRust => format!("{} · {}", self.advanced, self.rust),
+            AppMode::CodeTestTypescript => format!("{} · {}", self.advanced, self.typescript),
+            AppMode::CodeTestPython => format!("{} · {}", self.advanced, self.python),
+            AppMode::CustomKeymaps => self.custom_keymaps.clone(),
+            AppMode::VimMotions => self.vim_motions.clone(),
+            AppMode::CustomFile => self.custom_file.clone(),
+            _ => format!("{:?}", mode),
+        }
+    }
 }
@@ -1,14 +1,19 @@
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AppMode {
     Menu,
     Settings,
     About,
+    History,
+    Results,
     WordsCommandsMenu,
     VimCommands,
+    VimMotions,
     WordsMenu,
     WordsSimple,
+    WordsWeakKeys,
     WordsLua,
     WordsRuby,
     WordsRust,
@@ -18,6 +23,11 @@ pub enum AppMode {
     SentencesNormal,
     SentencesDvorak,
     SentencesQwerty,
+    SentencesColemak,
+    SentencesColemakDh,
+    SentencesWorkman,
+    SentencesNorman,
+    SentencesJapanese,
     CodeTestMenu,
     CodeTestLua,
     CodeTestRuby,
@@ -25,15 +35,10 @@ pub enum AppMode {
     CodeTestTypescript,
     CodeTestPython,
     CustomKeymaps,
+    CustomFile,
     CountSelection,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum KeyboardLayout {
-    Dvorak,
-    Qwerty,
-}
-
 #[derive(Debug, Clone)]
 pub struct PressedKey {
     pub display: String,
@@ -53,6 +58,19 @@ impl PressedKey {
     }
 }
 
+// Which modifier keys to light on the keyboard, side by side
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ModifierState {
+    pub left_shift: bool,
+    pub right_shift: bool,
+    pub caps_lock: bool,
+    pub left_ctrl: bool,
+    pub right_ctrl: bool,
+    pub left_alt: bool,
+    pub right_alt: bool,
+    pub super_key: bool,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ExerciseCount {
     All,
@@ -60,6 +78,10 @@ pub enum ExerciseCount {
     Count25,
     Count50,
     Count100,
+    Seconds15,
+    Seconds30,
+    Seconds60,
+    Seconds120,
 }
 
 impl ExerciseCount {
@@ -70,6 +92,17 @@ impl ExerciseCount {
             ExerciseCount::Count25 => Some(25),
             ExerciseCount::Count50 => Some(50),
             ExerciseCount::Count100 => Some(100),
+            _ => None,
+        }
+    }
+
+    pub fn to_duration(&self) -> Option<Duration> {
+        match self {
+            ExerciseCount::Seconds15 => Some(Duration::from_secs(15)),
+            ExerciseCount::Seconds30 => Some(Duration::from_secs(30)),
+            ExerciseCount::Seconds60 => Some(Duration::from_secs(60)),
+            ExerciseCount::Seconds120 => Some(Duration::from_secs(120)),
+            _ => None,
         }
     }
 
@@ -80,6 +113,72 @@ impl ExerciseCount {
             ExerciseCount::Count25 => "25",
             ExerciseCount::Count50 => "50",
             ExerciseCount::Count100 => "100",
+            ExerciseCount::Seconds15 => "15s",
+            ExerciseCount::Seconds30 => "30s",
+            ExerciseCount::Seconds60 => "60s",
+            ExerciseCount::Seconds120 => "120s",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistorySort {
+    Newest,
+    Oldest,
+    Wpm,
+    Accuracy,
+}
+
+impl HistorySort {
+    pub fn next(&self) -> Self {
+        match self {
+            HistorySort::Newest => HistorySort::Oldest,
+            HistorySort::Oldest => HistorySort::Wpm,
+            HistorySort::Wpm => HistorySort::Accuracy,
+            HistorySort::Accuracy => HistorySort::Newest,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeatmapMode {
+    Off,
+    SessionErrors,
+    SessionLatency,
+    HistoryErrors,
+    HistoryLatency,
+}
+
+impl HeatmapMode {
+    pub fn next(&self) -> Self {
+        match self {
+            HeatmapMode::Off => HeatmapMode::SessionErrors,
+            HeatmapMode::SessionErrors => HeatmapMode::SessionLatency,
+            HeatmapMode::SessionLatency => HeatmapMode::HistoryErrors,
+            HeatmapMode::HistoryErrors => HeatmapMode::HistoryLatency,
+            HeatmapMode::HistoryLatency => HeatmapMode::Off,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MistakePolicy {
+    // Wrong keys are counted but the cursor waits for the right one
+    #[default]
+    Block,
+    // Wrong keys are inserted and have to be backspaced before finishing
+    Free,
+    // The first wrong key ends the session
+    SuddenDeath,
+}
+
+impl MistakePolicy {
+    pub fn next(&self) -> Self {
+        match self {
+            MistakePolicy::Block => MistakePolicy::Free,
+            MistakePolicy::Free => MistakePolicy::SuddenDeath,
+            MistakePolicy::SuddenDeath => MistakePolicy::Block,
         }
     }
 }
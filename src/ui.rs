@@ -1,6 +1,12 @@
 use crate::app::App;
-use crate::keyboard::KeyboardRenderer;
-use crate::types::{AppMode, KeyboardLayout};
+use crate::exercises::{TokenKind, VimGoal, highlight_code};
+use crate::keyboard::{Heatmap, HeatmapMetric, KeyboardRenderer, finger_color};
+use crate::keymaps::Keymap;
+use crate::layout::Finger;
+use crate::romaji;
+use crate::text::{display_width, graphemes};
+use crate::types::{AppMode, HeatmapMode, HistorySort};
+use crate::vim_buffer::VimMode;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -20,7 +26,11 @@ pub fn render(f: &mut Frame, app: &App) {
         .split(f.size());
 
     render_top_panel(f, chunks[0], app);
-    render_keyboard_panel(f, chunks[1], app);
+    if app.mode == AppMode::History {
+        render_history_panel(f, chunks[1], app);
+    } else {
+        render_keyboard_panel(f, chunks[1], app);
+    }
     render_footer(f, chunks[2], app);
 }
 
@@ -35,7 +45,11 @@ fn render_top_panel(f: &mut Frame, area: Rect, app: &App) {
 }
 
 fn render_left_panel(f: &mut Frame, area: Rect, app: &App) {
-    let content = build_left_panel_content(app);
+    let content = build_left_panel_content(
+        app,
+        area.width.saturating_sub(2),
+        area.height.saturating_sub(2),
+    );
     let title = get_panel_title(app);
 
     let block = Block::default()
@@ -45,32 +59,37 @@ fn render_left_panel(f: &mut Frame, area: Rect, app: &App) {
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Cyan));
 
-    let paragraph = Paragraph::new(content)
-        .block(block)
-        .wrap(Wrap { trim: true });
+    // Practice content is laid out by hand and keeps its indentation
+    let paragraph = Paragraph::new(content).block(block).wrap(Wrap {
+        trim: !app.is_practice_mode(),
+    });
 
     f.render_widget(paragraph, area);
 }
 
-fn build_left_panel_content(app: &App) -> Vec<Line<'_>> {
+fn build_left_panel_content(app: &App, width: u16, height: u16) -> Vec<Line<'_>> {
     match app.mode {
         AppMode::Menu => build_menu_content(app),
         AppMode::Settings => build_settings_content(app),
         AppMode::About => build_about_content(app),
+        AppMode::History => build_history_summary_content(app),
+        AppMode::Results => build_results_content(app),
         AppMode::CountSelection => build_count_selection_content(app),
         AppMode::WordsCommandsMenu
         | AppMode::WordsMenu
         | AppMode::CodeTestMenu
         | AppMode::SentencesMenu => build_submenu_content(app),
-        _ => build_practice_content(app),
+        _ => build_practice_content(app, width, height),
     }
 }
 
 fn build_menu_content(app: &App) -> Vec<Line<'_>> {
-    let menu_items = vec![
+    let menu_items = [
         &app.translations.words_commands,
         &app.translations.sentence_practice,
         &app.translations.real_code_test,
+        &app.translations.custom_file,
+        &app.translations.history,
         &app.translations.settings,
         &app.translations.about,
     ];
@@ -114,7 +133,7 @@ fn build_settings_content(app: &App) -> Vec<Line<'_>> {
             ),
             Span::raw(format!(
                 "{}: {}",
-                app.translations.keyboard_layout, app.current_layout_name
+                app.translations.keyboard_layout, app.keyboard_layout.name
             )),
         ]),
         Line::from(vec![
@@ -128,6 +147,58 @@ fn build_settings_content(app: &App) -> Vec<Line<'_>> {
             ),
             Span::raw(format!("{}: {}", app.translations.language, current_lang)),
         ]),
+        Line::from(vec![
+            Span::styled(
+                if app.submenu_selected == 2 {
+                    " ❯ "
+                } else {
+                    "   "
+                },
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(format!(
+                "{}: {}",
+                app.translations.mistake_policy,
+                app.translations.policy_name(app.mistake_policy)
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                if app.submenu_selected == 3 {
+                    " ❯ "
+                } else {
+                    "   "
+                },
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(format!(
+                "{}: {}",
+                app.translations.skip_indentation,
+                if app.skip_indentation {
+                    &app.translations.on
+                } else {
+                    &app.translations.off
+                }
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                if app.submenu_selected == 4 {
+                    " ❯ "
+                } else {
+                    "   "
+                },
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(format!(
+                "{}: {}",
+                app.translations.layout_emulation,
+                match &app.emulation_source {
+                    Some(source) => format!("{} → {}", source.name, app.keyboard_layout.name),
+                    None => app.translations.off.clone(),
+                }
+            )),
+        ]),
     ]
 }
 
@@ -177,18 +248,291 @@ fn build_about_content(app: &App) -> Vec<Line<'_>> {
     ]
 }
 
+fn build_results_content(app: &App) -> Vec<Line<'_>> {
+    let Some(results) = &app.results else {
+        return vec![Line::from("")];
+    };
+    let record = &results.record;
+
+    let comparison = match results.previous_best {
+        _ if results.is_new_best() => Span::styled(
+            format!("  ★ {}", app.translations.new_best),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Some(best) => Span::styled(
+            format!(
+                "  {}: {:.1} ({:+.1})",
+                app.translations.previous_best,
+                best,
+                record.wpm - best
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+        None => Span::raw(""),
+    };
+
+    let mut slowest = vec![Span::styled(
+        format!("{}: ", app.translations.slowest_keys),
+        Style::default().fg(Color::Yellow),
+    )];
+    for stat in &results.slowest_keys {
+        slowest.push(Span::styled(
+            format_special_char(stat.key),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ));
+        slowest.push(Span::raw(format!(
+            " {:.0}ms  ",
+            stat.average_latency_ms().unwrap_or(0.0)
+        )));
+    }
+
+    let options = [&app.translations.retry, &app.translations.back_to_menu];
+    let mut option_spans = Vec::new();
+    for (i, option) in options.iter().enumerate() {
+        option_spans.push(Span::styled(
+            if app.submenu_selected == i {
+                " ❯ "
+            } else {
+                "   "
+            },
+            Style::default().fg(Color::Yellow),
+        ));
+        option_spans.push(Span::raw(option.to_string()));
+    }
+
+    let mut title = vec![
+        Span::styled(
+            app.translations.mode_name(&record.mode),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" · {}", app.translations.policy_name(record.mistake_policy)),
+            Style::default().fg(Color::Gray),
+        ),
+    ];
+    if results.exercises_failed > 0 {
+        title.push(Span::styled(
+            format!(
+                " ✗ {} {}",
+                results.exercises_failed, app.translations.ended_on_error
+            ),
+            Style::default().fg(Color::Red),
+        ));
+    }
+
+    vec![
+        Line::from(title),
+        Line::from(vec![
+            Span::styled(
+                format!("⚡ {}: ", app.translations.wpm),
+                Style::default().fg(Color::Magenta),
+            ),
+            Span::raw(format!("{:.1}", record.wpm)),
+            comparison,
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("   {}: ", app.translations.raw_wpm),
+                Style::default().fg(Color::Magenta),
+            ),
+            Span::raw(format!("{:.1}", record.raw_wpm)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("📊 {}: ", app.translations.accuracy),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw(format!("{:.1}%", record.accuracy)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("✗ {}: ", app.translations.errors),
+                Style::default().fg(Color::Red),
+            ),
+            Span::raw(format!("{}", record.errors)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("⏱ {}: ", app.translations.duration),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(format!("{:.1}s", record.duration_secs)),
+        ]),
+        Line::from(slowest),
+        Line::from(""),
+        Line::from(option_spans),
+    ]
+}
+
+fn history_sort_name(app: &App) -> &str {
+    match app.history_sort {
+        HistorySort::Newest => &app.translations.newest,
+        HistorySort::Oldest => &app.translations.oldest,
+        HistorySort::Wpm => &app.translations.wpm,
+        HistorySort::Accuracy => &app.translations.accuracy,
+    }
+}
+
+fn build_history_summary_content(app: &App) -> Vec<Line<'_>> {
+    let records = app.history.query(
+        app.history_mode_filter.as_ref(),
+        app.history_layout_filter.as_deref(),
+        app.history_sort,
+    );
+    let best_wpm = records.iter().map(|r| r.wpm).fold(0.0_f32, f32::max);
+    let mode_filter = match &app.history_mode_filter {
+        Some(mode) => app.translations.mode_name(mode),
+        None => app.translations.all.clone(),
+    };
+    let layout_filter = match &app.history_layout_filter {
+        Some(layout) => layout.clone(),
+        None => app.translations.all.clone(),
+    };
+
+    vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                format!("{}: ", app.translations.sort),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(history_sort_name(app).to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("{}: ", app.translations.mode),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(mode_filter),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("{}: ", app.translations.layout),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(layout_filter),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                format!("{}: ", app.translations.sessions),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::raw(format!("{}", records.len())),
+            Span::styled(
+                format!("   {} {}: ", app.translations.best, app.translations.wpm),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::raw(format!("{:.1}", best_wpm)),
+        ]),
+    ]
+}
+
+fn render_history_panel(f: &mut Frame, area: Rect, app: &App) {
+    let records = app.history.query(
+        app.history_mode_filter.as_ref(),
+        app.history_layout_filter.as_deref(),
+        app.history_sort,
+    );
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "{:<17} {:<32} {:<18} {:>6} {:>7} {:>6} {:>8}",
+            app.translations.date,
+            app.translations.mode,
+            app.translations.layout,
+            app.translations.wpm,
+            "%",
+            app.translations.errors,
+            app.translations.duration,
+        ),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ))];
+
+    if records.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            app.translations.no_history.as_str(),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let scroll = app.history_scroll.min(records.len().saturating_sub(1));
+    for record in records.iter().skip(scroll) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:<17} ", record.formatted_timestamp()),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::raw(format!(
+                "{:<32} {:<18} ",
+                truncate(&app.translations.mode_name(&record.mode), 32),
+                truncate(&record.layout, 18)
+            )),
+            Span::styled(
+                format!("{:>6.1} ", record.wpm),
+                Style::default().fg(Color::Magenta),
+            ),
+            Span::styled(
+                format!("{:>6.1}% ", record.accuracy),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(
+                format!("{:>6} ", record.errors),
+                Style::default().fg(Color::Red),
+            ),
+            Span::raw(format!("{:>7.0}s", record.duration_secs)),
+        ]));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(app.translations.history.as_str())
+        .title_alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Green));
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let mut truncated: String = text.chars().take(max - 1).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
 fn build_submenu_content(app: &App) -> Vec<Line<'_>> {
     let items = if app.mode == AppMode::WordsCommandsMenu {
         vec![
             &app.translations.simple_words,
             &app.translations.vim_commands,
             &app.translations.words_by_language,
+            &app.translations.weak_keys_drill,
+            &app.translations.custom_keymaps,
+            &app.translations.vim_motions,
         ]
     } else if app.mode == AppMode::SentencesMenu {
         vec![
             &app.translations.sentences_normal,
             &app.translations.sentences_dvorak,
             &app.translations.sentences_qwerty,
+            &app.translations.sentences_colemak,
+            &app.translations.sentences_colemak_dh,
+            &app.translations.sentences_workman,
+            &app.translations.sentences_norman,
+            &app.translations.sentences_japanese,
         ]
     } else if app.mode == AppMode::WordsMenu || app.mode == AppMode::CodeTestMenu {
         vec![
@@ -220,7 +564,8 @@ fn build_submenu_content(app: &App) -> Vec<Line<'_>> {
 }
 
 fn build_count_selection_content(app: &App) -> Vec<Line<'_>> {
-    let counts = vec!["All", "10", "25", "50", "100"];
+    let counts = ["All", "10", "25", "50", "100"];
+    let limits = ["15s", "30s", "60s", "120s"];
 
     let mut lines = vec![
         Line::from(""),
@@ -233,25 +578,44 @@ fn build_count_selection_content(app: &App) -> Vec<Line<'_>> {
         Line::from(""),
     ];
 
-    for (i, count) in counts.iter().enumerate() {
-        lines.push(Line::from(vec![
-            Span::styled(
-                if app.count_selected == i {
-                    " ❯ "
-                } else {
-                    "   "
-                },
-                Style::default().fg(Color::Yellow),
-            ),
-            Span::raw(count.to_string()),
-        ]));
+    // Exercise counts and time limits side by side so every option fits the
+    // top panel
+    let marker = |i: usize| {
+        Span::styled(
+            if app.count_selected == i {
+                " ❯ "
+            } else {
+                "   "
+            },
+            Style::default().fg(Color::Yellow),
+        )
+    };
+    for (row, count) in counts.iter().enumerate() {
+        let mut spans = vec![marker(row), Span::raw(format!("{:<8}", count))];
+        if let Some(limit) = limits.get(row) {
+            spans.push(marker(counts.len() + row));
+            spans.push(Span::raw(limit.to_string()));
+        }
+        lines.push(Line::from(spans));
     }
     lines
 }
 
-fn build_practice_content(app: &App) -> Vec<Line<'_>> {
+fn build_practice_content(app: &App, width: u16, height: u16) -> Vec<Line<'_>> {
     let mut lines = vec![Line::from("")];
 
+    if app.mode == AppMode::CustomFile && app.custom_exercises.is_empty() {
+        lines.push(Line::from(Span::styled(
+            app.translations.no_custom_exercises.as_str(),
+            Style::default().fg(Color::DarkGray),
+        )));
+        return lines;
+    }
+
+    if app.mode == AppMode::VimMotions {
+        return build_vim_motions_content(app);
+    }
+
     if let Some(limit) = app.exercise_count.to_usize() {
         lines.push(Line::from(vec![Span::styled(
             format!("Exercise: {}/{} ", app.exercises_completed + 1, limit),
@@ -260,53 +624,424 @@ fn build_practice_content(app: &App) -> Vec<Line<'_>> {
         lines.push(Line::from(""));
     }
 
-    let mut target_spans = vec![Span::styled(
-        format!("{}: ", app.translations.target),
-        Style::default().fg(Color::Gray),
-    )];
+    if app.is_code_exercise() {
+        let visible = (height as usize).saturating_sub(lines.len());
+        lines.extend(build_code_lines(app, visible));
+        return lines;
+    }
 
-    for (i, ch) in app.target_text.chars().enumerate() {
-        let display_char = format_special_char(ch);
-        let style = if i < app.typed_text.len() {
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::DIM)
-        } else if i == app.current_key_index {
-            Style::default()
-                .fg(Color::Yellow)
-                .bg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Cyan)
-        };
-        target_spans.push(Span::styled(display_char, style));
+    let mut label = &app.translations.target;
+    if app.mode == AppMode::SentencesJapanese
+        && let Some(sentence) = &app.japanese_sentence
+    {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{}: ", app.translations.target),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::styled(
+                sentence.text,
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        label = &app.translations.reading;
+    }
+
+    let prefix = Span::styled(format!("{}: ", label), Style::default().fg(Color::Gray));
+
+    if app.mode == AppMode::CustomKeymaps {
+        let mut target_spans = vec![prefix];
+        for (i, chord) in app.target_chords.iter().enumerate() {
+            target_spans.push(Span::styled(chord.display(), target_style(app, i, true)));
+        }
+        lines.push(Line::from(target_spans));
+    } else {
+        lines.extend(build_target_lines(app, prefix, width));
     }
-    lines.push(Line::from(target_spans));
     lines.push(Line::from(""));
 
+    if app.mode == AppMode::CustomKeymaps
+        && let Some(keymap) = app.current_keymap()
+    {
+        lines.extend(build_keymap_lines(app, keymap));
+    }
+
     let mut typed_spans = vec![Span::styled(
         format!("{}: ", app.translations.typed),
         Style::default().fg(Color::Gray),
     )];
 
-    for ch in app.typed_text.chars() {
-        let display_char = format_special_char(ch);
+    let mut target = graphemes(&app.target_text).into_iter();
+    for grapheme in graphemes(&app.typed_text) {
+        let color = if app.mode == AppMode::CustomKeymaps || target.next() == Some(grapheme) {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        typed_spans.push(Span::styled(
+            format_special_grapheme(grapheme),
+            Style::default().fg(color),
+        ));
+    }
+    if app.mode == AppMode::SentencesJapanese {
         typed_spans.push(Span::styled(
-            display_char,
-            Style::default().fg(Color::Green),
+            app.pending_input.as_str(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::UNDERLINED),
         ));
     }
     lines.push(Line::from(typed_spans));
 
+    if app.mode == AppMode::SentencesJapanese {
+        let rest: Vec<char> = graphemes(&app.target_text)
+            .into_iter()
+            .skip(app.current_key_index)
+            .flat_map(|g| g.chars())
+            .collect();
+        if let Some(hint) = romaji::hint(&rest, &app.pending_input) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{}: ", app.translations.romaji),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::styled(
+                    app.pending_input.as_str(),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::styled(
+                    hint.chars()
+                        .skip(app.pending_input.chars().count())
+                        .collect::<String>(),
+                    Style::default().fg(Color::Cyan),
+                ),
+            ]));
+        }
+    }
+
+    lines
+}
+
+fn build_vim_motions_content(app: &App) -> Vec<Line<'_>> {
+    let (Some(challenge), Some(buffer)) = (&app.vim_challenge, &app.vim_buffer) else {
+        return vec![];
+    };
+    let label = Style::default().fg(Color::Gray);
+
+    let mut goal_spans = Vec::new();
+    if let Some(limit) = app.exercise_count.to_usize() {
+        goal_spans.push(Span::styled(
+            format!("{}/{} ", app.exercises_completed + 1, limit),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    goal_spans.push(Span::styled(format!("{}: ", app.translations.goal), label));
+    goal_spans.push(Span::styled(
+        challenge.description,
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ));
+    let mut lines = vec![Line::from(goal_spans)];
+
+    if let VimGoal::Text(expected) = challenge.goal {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", app.translations.expected), label),
+            Span::styled(
+                expected.replace('\n', "↵"),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    let goal_cursor = match challenge.goal {
+        VimGoal::Cursor(row, col) => Some((row, col)),
+        VimGoal::Text(_) => None,
+    };
+    for (row, line) in buffer.lines.iter().enumerate() {
+        let mut spans = Vec::new();
+        // One extra cell so the cursor stays visible on empty lines and at the
+        // end of a line in insert mode
+        for col in 0..=line.len() {
+            let ch = line.get(col).copied().unwrap_or(' ');
+            let style = if (row, col) == (buffer.row, buffer.col) {
+                match buffer.mode {
+                    VimMode::Normal => Style::default().fg(Color::Black).bg(Color::Yellow),
+                    VimMode::Insert => Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::UNDERLINED),
+                }
+            } else if goal_cursor == Some((row, col)) {
+                Style::default().fg(Color::Black).bg(Color::Magenta)
+            } else {
+                Style::default().fg(Color::Cyan)
+            };
+            spans.push(Span::styled(ch.to_string(), style));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+
+    let mode = match buffer.mode {
+        VimMode::Normal => "-- NORMAL --",
+        VimMode::Insert => "-- INSERT --",
+    };
+    lines.push(Line::from(vec![
+        Span::styled(mode, Style::default().fg(Color::Green)),
+        Span::styled(
+            format!(" {:<4}", buffer.pending),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled(
+            format!(
+                "{}: {}  {}: {}",
+                app.translations.keystrokes,
+                app.vim_keystrokes,
+                app.translations.par,
+                challenge.par()
+            ),
+            label,
+        ),
+    ]));
+
+    if let Some((keystrokes, previous)) = &app.vim_last_score {
+        let color = if *keystrokes <= previous.par() {
+            Color::Green
+        } else {
+            Color::Yellow
+        };
+        lines.push(Line::from(Span::styled(
+            format!(
+                "✓ {} {} ({} {}: {})",
+                app.translations.solved_in,
+                keystrokes,
+                app.translations.par,
+                previous.par(),
+                previous.solution
+            ),
+            Style::default().fg(color),
+        )));
+    }
+    lines
+}
+
+// The target is wrapped by hand so every row can carry a row underneath with
+// the wrong keys that were hit at each position. Rows are measured in terminal
+// cells so wide CJK characters and emoji keep both rows aligned
+fn build_target_lines<'a>(app: &'a App, prefix: Span<'a>, width: u16) -> Vec<Line<'a>> {
+    let indent = " ".repeat(prefix.width());
+    let row_width = (width as usize).saturating_sub(prefix.width()).max(10);
+    let typed = graphemes(&app.typed_text);
+    let target = graphemes(&app.target_text);
+
+    let mut lines = Vec::new();
+    let mut prefix = Some(prefix);
+    let mut target_spans = Vec::new();
+    let mut miss_spans = Vec::new();
+    let mut has_miss = false;
+    let mut used = 0;
+
+    for (i, grapheme) in target.iter().enumerate() {
+        let shown = format_special_grapheme(grapheme);
+        let miss = app.typed_errors.get(&i).map(|c| format_special_char(*c));
+        let cell = display_width(&shown).max(miss.as_deref().map_or(1, display_width));
+
+        if target_spans.is_empty() || used + cell > row_width {
+            if !target_spans.is_empty() {
+                lines.push(Line::from(std::mem::take(&mut target_spans)));
+                let misses = std::mem::take(&mut miss_spans);
+                if has_miss {
+                    lines.push(Line::from(misses));
+                }
+            }
+            let lead = prefix.take().unwrap_or_else(|| Span::raw(indent.clone()));
+            target_spans.push(lead);
+            miss_spans.push(Span::raw(indent.clone()));
+            has_miss = false;
+            used = 0;
+        }
+
+        let correct = typed.get(i).is_none_or(|t| t == grapheme);
+        let mut style = target_style(app, i, correct);
+        match miss {
+            Some(wrong) => {
+                has_miss = true;
+                style = style.bg(Color::Red);
+                let pad = cell - display_width(&wrong);
+                miss_spans.push(Span::styled(
+                    format!("{}{}", wrong, " ".repeat(pad)),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            None => miss_spans.push(Span::raw(" ".repeat(cell))),
+        }
+        let pad = cell - display_width(&shown);
+        target_spans.push(Span::styled(format!("{}{}", shown, " ".repeat(pad)), style));
+        used += cell;
+    }
+
+    if !target_spans.is_empty() {
+        lines.push(Line::from(target_spans));
+        if has_miss {
+            lines.push(Line::from(miss_spans));
+        }
+    }
+    lines
+}
+
+// Code is shown as an editor-like block with line numbers; the view scrolls so
+// the line being typed stays roughly centred
+fn build_code_lines(app: &App, visible: usize) -> Vec<Line<'_>> {
+    let typed = graphemes(&app.typed_text);
+    let target = graphemes(&app.target_text);
+    // Token kinds come per char; each grapheme takes the kind of its first one
+    let highlights = app.code_language().and_then(|lang| {
+        let kinds = highlight_code(&app.target_text, lang)?;
+        let mut offset = 0;
+        Some(
+            target
+                .iter()
+                .map(|g| {
+                    let kind = kinds[offset];
+                    offset += g.chars().count();
+                    kind
+                })
+                .collect::<Vec<_>>(),
+        )
+    });
+    let mut rows: Vec<Vec<(usize, &str)>> = vec![Vec::new()];
+    let mut cursor_row = 0;
+    for (i, grapheme) in target.iter().enumerate() {
+        if i == app.current_key_index {
+            cursor_row = rows.len() - 1;
+        }
+        if let Some(row) = rows.last_mut() {
+            row.push((i, grapheme));
+        }
+        if *grapheme == "\n" {
+            rows.push(Vec::new());
+        }
+    }
+    if app.current_key_index >= target.len() {
+        cursor_row = rows.len() - 1;
+    }
+
+    let visible = visible.max(1);
+    let first = cursor_row
+        .saturating_sub(visible / 2)
+        .min(rows.len().saturating_sub(visible));
+    let number_width = rows.len().to_string().len();
+
+    let mut lines = Vec::new();
+    for (row, chars) in rows.iter().enumerate().skip(first).take(visible) {
+        let number_color = if row == cursor_row {
+            Color::Yellow
+        } else {
+            Color::DarkGray
+        };
+        let mut spans = vec![Span::styled(
+            format!("{:>width$} │ ", row + 1, width = number_width),
+            Style::default().fg(number_color),
+        )];
+        for &(i, grapheme) in chars {
+            let correct = typed.get(i).is_none_or(|t| *t == grapheme);
+            let mut style = match highlights.as_ref().map(|kinds| kinds[i]) {
+                Some(kind) if i != app.current_key_index && correct => {
+                    let style = Style::default().fg(token_color(kind));
+                    if i < app.current_key_index {
+                        style.add_modifier(Modifier::DIM)
+                    } else {
+                        style
+                    }
+                }
+                _ => target_style(app, i, correct),
+            };
+            if app.typed_errors.contains_key(&i) {
+                style = style.bg(Color::Red);
+            }
+            let display = match grapheme {
+                "\n" => "↵".to_string(),
+                "\t" => "    ".to_string(),
+                _ => grapheme.to_string(),
+            };
+            spans.push(Span::styled(display, style));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+fn token_color(kind: TokenKind) -> Color {
+    match kind {
+        TokenKind::Keyword => Color::Magenta,
+        TokenKind::Function => Color::Blue,
+        TokenKind::String => Color::Green,
+        TokenKind::Comment => Color::DarkGray,
+        TokenKind::Number => Color::LightRed,
+        TokenKind::Punctuation => Color::Gray,
+        TokenKind::Plain => Color::Cyan,
+    }
+}
+
+fn target_style(app: &App, index: usize, correct: bool) -> Style {
+    if index < app.current_key_index && !correct {
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+    } else if index < app.current_key_index {
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::DIM)
+    } else if index == app.current_key_index {
+        Style::default()
+            .fg(Color::Yellow)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Cyan)
+    }
+}
+
+fn build_keymap_lines<'a>(app: &'a App, keymap: &'a Keymap) -> Vec<Line<'a>> {
+    let mut lines = Vec::new();
+    let label = Style::default().fg(Color::Gray);
+    let value = Style::default().fg(Color::Magenta);
+
+    if let Some(desc) = &keymap.desc {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", app.translations.description), label),
+            Span::styled(desc.as_str(), value),
+        ]));
+    }
+    if let Some(mode) = &keymap.mode {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", app.translations.mode), label),
+            Span::styled(mode.as_str(), Style::default().fg(Color::Yellow)),
+        ]));
+    }
+    if let Some(rhs) = &keymap.rhs {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", app.translations.maps_to), label),
+            Span::styled(rhs.as_str(), value),
+        ]));
+    }
+    if !lines.is_empty() {
+        lines.push(Line::from(""));
+    }
     lines
 }
 
 fn format_special_char(ch: char) -> String {
-    match ch {
-        '\n' => "↵".to_string(),
-        '\t' => "⇥".to_string(),
-        ' ' => "·".to_string(),
-        _ => ch.to_string(),
+    format_special_grapheme(ch.encode_utf8(&mut [0; 4]))
+}
+
+fn format_special_grapheme(grapheme: &str) -> String {
+    match grapheme {
+        "\n" => "↵".to_string(),
+        "\t" => "⇥".to_string(),
+        " " => "·".to_string(),
+        _ => grapheme.to_string(),
     }
 }
 
@@ -315,6 +1050,8 @@ fn get_panel_title(app: &App) -> String {
         AppMode::Menu => format!(" LazyDvorak - {} ", app.translations.main_menu),
         AppMode::Settings => app.translations.settings.clone(),
         AppMode::About => app.translations.about.clone(),
+        AppMode::History => app.translations.history.clone(),
+        AppMode::Results => app.translations.results.clone(),
         AppMode::CountSelection => app.translations.select_count.clone(),
         AppMode::WordsCommandsMenu
         | AppMode::WordsMenu
@@ -324,6 +1061,49 @@ fn get_panel_title(app: &App) -> String {
     }
 }
 
+fn build_weak_keys_line(app: &App) -> Line<'_> {
+    let weakest = app.stats.weakest_keys(3);
+    if weakest.is_empty() {
+        return Line::from("");
+    }
+
+    let mut spans = vec![Span::styled(
+        format!("🎯 {}: ", app.translations.weak_keys),
+        Style::default().fg(Color::Red),
+    )];
+    for stat in weakest {
+        spans.push(Span::styled(
+            format_special_char(stat.key),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ));
+        let latency = match stat.average_latency_ms() {
+            Some(ms) => format!(" {:.0}ms", ms),
+            None => String::new(),
+        };
+        spans.push(Span::raw(format!(
+            " {:.0}%{}  ",
+            stat.error_rate(),
+            latency
+        )));
+    }
+    Line::from(spans)
+}
+
+fn build_time_left_line(app: &App) -> Line<'_> {
+    match app.time_remaining() {
+        Some(remaining) if app.is_practice_mode() => Line::from(vec![
+            Span::styled(
+                format!("⏱ {}: ", app.translations.time_left),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(format!("{}s", remaining.as_secs_f32().ceil() as u64)),
+        ]),
+        _ => Line::from(""),
+    }
+}
+
 fn render_stats_panel(f: &mut Frame, area: Rect, app: &App) {
     let wpm = app.stats.calculate_wpm();
     let accuracy = app.stats.calculate_accuracy();
@@ -358,13 +1138,14 @@ fn render_stats_panel(f: &mut Frame, area: Rect, app: &App) {
             ),
             Span::raw(format!("{:.1}", wpm)),
         ]),
-        Line::from(""),
+        build_weak_keys_line(app),
+        build_time_left_line(app),
         Line::from(vec![
             Span::styled(
                 format!("{}: ", app.translations.layout),
                 Style::default().fg(Color::Gray),
             ),
-            Span::styled(&app.current_layout_name, Style::default().fg(Color::Green)),
+            Span::styled(&app.keyboard_layout.name, Style::default().fg(Color::Green)),
         ]),
     ];
 
@@ -390,22 +1171,58 @@ fn render_keyboard_panel(f: &mut Frame, area: Rect, app: &App) {
     if app.alt_pressed {
         modifiers_str.push_str(&format!(" [{}]", app.translations.alt));
     }
+    if app.caps_lock {
+        modifiers_str.push_str(&format!(" [{}]", app.translations.caps_lock));
+    }
 
-    let keyboard_lines = match app.keyboard_layout {
-        KeyboardLayout::Dvorak => {
-            KeyboardRenderer::render_dvorak(app.shift_pressed, &app.last_pressed_key)
+    let heatmap = match app.heatmap_mode {
+        HeatmapMode::Off => None,
+        HeatmapMode::SessionErrors => Some(Heatmap::new(
+            HeatmapMetric::ErrorRate,
+            &app.stats.key_stats(),
+        )),
+        HeatmapMode::SessionLatency => {
+            Some(Heatmap::new(HeatmapMetric::Latency, &app.stats.key_stats()))
+        }
+        HeatmapMode::HistoryErrors => Some(Heatmap::new(
+            HeatmapMetric::ErrorRate,
+            &app.history_key_stats,
+        )),
+        HeatmapMode::HistoryLatency => {
+            Some(Heatmap::new(HeatmapMetric::Latency, &app.history_key_stats))
         }
-        KeyboardLayout::Qwerty => {
-            KeyboardRenderer::render_qwerty(app.shift_pressed, &app.last_pressed_key)
+    };
+    let heatmap_label = match app.heatmap_mode {
+        HeatmapMode::Off => String::new(),
+        HeatmapMode::SessionErrors => format!(" [{}]", app.translations.heatmap_session_errors),
+        HeatmapMode::SessionLatency => {
+            format!(" [{}]", app.translations.heatmap_session_latency)
+        }
+        HeatmapMode::HistoryErrors => format!(" [{}]", app.translations.heatmap_history_errors),
+        HeatmapMode::HistoryLatency => {
+            format!(" [{}]", app.translations.heatmap_history_latency)
         }
     };
 
+    let next_key = app.next_expected_char();
+    let mut keyboard_lines = KeyboardRenderer::render(
+        &app.keyboard_layout,
+        app.shift_pressed,
+        &app.last_pressed_key,
+        heatmap.as_ref(),
+        next_key,
+        app.modifier_state(),
+    );
+    if let Some(c) = next_key {
+        keyboard_lines.push(build_next_key_hint(app, c));
+    }
+
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .title(format!(
-            "{} - {}{}",
-            app.translations.keyboard, app.current_layout_name, modifiers_str
+            "{} - {}{}{}",
+            app.translations.keyboard, app.keyboard_layout.name, modifiers_str, heatmap_label
         ))
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Green));
@@ -417,16 +1234,58 @@ fn render_keyboard_panel(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(paragraph, area);
 }
 
+fn build_next_key_hint(app: &App, c: char) -> Line<'_> {
+    let finger = if c == ' ' {
+        Some((
+            app.translations.finger_thumb.clone(),
+            finger_color(Finger::RightThumb),
+        ))
+    } else {
+        app.keyboard_layout
+            .key_for(c)
+            .and_then(|key| key.finger)
+            .map(|f| (app.translations.finger_name(f), finger_color(f)))
+    };
+
+    let mut spans = vec![
+        Span::styled(
+            format!("{}: ", app.translations.next_key),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(
+            if app.keyboard_layout.needs_shift(c) {
+                format!(" {} + {} ", app.translations.shift, c)
+            } else {
+                format!(" {} ", format_special_char(c))
+            },
+            Style::default()
+                .fg(Color::Black)
+                .bg(finger.as_ref().map_or(Color::White, |(_, color)| *color))
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if let Some((name, color)) = finger {
+        spans.push(Span::styled(
+            format!("  {}", name),
+            Style::default().fg(color),
+        ));
+    }
+    Line::from(spans)
+}
+
 fn render_footer(f: &mut Frame, area: Rect, app: &App) {
     let instructions = match app.mode {
         AppMode::Menu => &app.translations.nav_menu,
+        AppMode::History => &app.translations.nav_history,
         AppMode::Settings
         | AppMode::About
+        | AppMode::Results
         | AppMode::CountSelection
         | AppMode::WordsCommandsMenu
         | AppMode::WordsMenu
         | AppMode::CodeTestMenu
         | AppMode::SentencesMenu => &app.translations.nav_submenu,
+        AppMode::VimMotions => &app.translations.nav_vim_motions,
         _ => &app.translations.nav_practice,
     };
 
@@ -0,0 +1,887 @@
+use crossterm::event::KeyCode;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VimMode {
+    Normal,
+    Insert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Motion {
+    Left,
+    Right,
+    Down,
+    Up,
+    NextLine,
+    WordStart(bool),
+    WordEnd(bool),
+    WordBack(bool),
+    LineStart,
+    FirstNonBlank,
+    LineEnd,
+    FirstLine,
+    LastLine,
+    Find(char, char),
+    RepeatFind(bool),
+    ParagraphNext,
+    ParagraphPrev,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    Move(Motion),
+    Operate(char, Motion),
+    OperateLine(char),
+    OperateObject(char, bool, char),
+    Replace(char),
+    Simple(char),
+}
+
+enum Parsed<T> {
+    Incomplete,
+    Invalid,
+    Done(T),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Target {
+    row: usize,
+    col: usize,
+    linewise: bool,
+    inclusive: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Register {
+    text: String,
+    linewise: bool,
+}
+
+type Pos = (usize, usize);
+
+// A small Vim emulation: normal-mode motions, counts, d/c/y operators with
+// motions and text objects, the common single-key edits, undo and a plain
+// insert mode. Enough to score motion drills, not a full editor
+#[derive(Debug, Clone)]
+pub struct VimBuffer {
+    pub lines: Vec<Vec<char>>,
+    pub row: usize,
+    pub col: usize,
+    pub mode: VimMode,
+    pub pending: String,
+    register: Option<Register>,
+    last_find: Option<(char, char)>,
+    undo: Vec<(Vec<Vec<char>>, Pos)>,
+}
+
+impl VimBuffer {
+    pub fn new(text: &str, row: usize, col: usize) -> Self {
+        let mut lines: Vec<Vec<char>> = text.split('\n').map(|l| l.chars().collect()).collect();
+        if lines.is_empty() {
+            lines.push(Vec::new());
+        }
+        let mut buffer = Self {
+            lines,
+            row,
+            col,
+            mode: VimMode::Normal,
+            pending: String::new(),
+            register: None,
+            last_find: None,
+            undo: Vec::new(),
+        };
+        buffer.clamp_cursor();
+        buffer
+    }
+
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|l| l.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.mode == VimMode::Normal && self.pending.is_empty()
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) {
+        match self.mode {
+            VimMode::Insert => self.handle_insert_key(code),
+            VimMode::Normal => self.handle_normal_key(code),
+        }
+    }
+
+    fn handle_insert_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => {
+                self.mode = VimMode::Normal;
+                self.col = self.col.saturating_sub(1);
+                self.clamp_cursor();
+            }
+            KeyCode::Char(c) => {
+                self.lines[self.row].insert(self.col, c);
+                self.col += 1;
+            }
+            KeyCode::Tab => {
+                for _ in 0..4 {
+                    self.lines[self.row].insert(self.col, ' ');
+                    self.col += 1;
+                }
+            }
+            KeyCode::Enter => {
+                let rest = self.lines[self.row].split_off(self.col);
+                self.lines.insert(self.row + 1, rest);
+                self.row += 1;
+                self.col = 0;
+            }
+            KeyCode::Backspace if self.col > 0 => {
+                self.col -= 1;
+                self.lines[self.row].remove(self.col);
+            }
+            KeyCode::Backspace if self.row > 0 => {
+                let line = self.lines.remove(self.row);
+                self.row -= 1;
+                self.col = self.lines[self.row].len();
+                self.lines[self.row].extend(line);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_normal_key(&mut self, code: KeyCode) {
+        let key = match code {
+            KeyCode::Char(c) => c,
+            KeyCode::Esc => {
+                self.pending.clear();
+                return;
+            }
+            KeyCode::Enter => '+',
+            KeyCode::Backspace | KeyCode::Left => 'h',
+            KeyCode::Right => 'l',
+            KeyCode::Down => 'j',
+            KeyCode::Up => 'k',
+            _ => return,
+        };
+        self.pending.push(key);
+
+        let keys: Vec<char> = self.pending.chars().collect();
+        match parse(&keys) {
+            Parsed::Incomplete => {}
+            Parsed::Invalid => self.pending.clear(),
+            Parsed::Done((count, command)) => {
+                self.pending.clear();
+                self.execute(count, command);
+                if self.mode == VimMode::Normal {
+                    self.clamp_cursor();
+                }
+            }
+        }
+    }
+
+    fn execute(&mut self, count: Option<usize>, command: Command) {
+        let n = count.unwrap_or(1);
+        match command {
+            Command::Move(motion) => {
+                if let Some(target) = self.motion_target(motion, count, false) {
+                    self.row = target.row;
+                    self.col = target.col;
+                }
+            }
+            Command::Operate(op, motion) => {
+                // cw behaves like ce when the cursor is on a word
+                let motion = match motion {
+                    Motion::WordStart(big)
+                        if op == 'c' && !self.char_at(self.row, self.col).is_whitespace() =>
+                    {
+                        Motion::WordEnd(big)
+                    }
+                    other => other,
+                };
+                if let Some(target) = self.motion_target(motion, count, true) {
+                    self.apply_operator(op, target);
+                }
+            }
+            Command::OperateLine(op) => {
+                let last = (self.row + n - 1).min(self.lines.len() - 1);
+                self.apply_operator(
+                    op,
+                    Target {
+                        row: last,
+                        col: 0,
+                        linewise: true,
+                        inclusive: true,
+                    },
+                );
+            }
+            Command::OperateObject(op, around, object) => {
+                if let Some((start, end)) = self.text_object(around, object) {
+                    self.row = start.0;
+                    self.col = start.1;
+                    self.apply_operator(
+                        op,
+                        Target {
+                            row: end.0,
+                            col: end.1,
+                            linewise: false,
+                            inclusive: false,
+                        },
+                    );
+                }
+            }
+            Command::Replace(c) => {
+                let line = &self.lines[self.row];
+                if self.col + n <= line.len() {
+                    self.save_undo();
+                    for i in 0..n {
+                        self.lines[self.row][self.col + i] = c;
+                    }
+                    self.col += n - 1;
+                }
+            }
+            Command::Simple(c) => self.execute_simple(c, count),
+        }
+    }
+
+    fn execute_simple(&mut self, c: char, count: Option<usize>) {
+        let n = count.unwrap_or(1);
+        match c {
+            'x' if !self.lines[self.row].is_empty() => {
+                self.execute(count, Command::Operate('d', Motion::Right))
+            }
+            'X' => self.execute(count, Command::Operate('d', Motion::Left)),
+            'D' => self.execute(count, Command::Operate('d', Motion::LineEnd)),
+            'C' => self.execute(count, Command::Operate('c', Motion::LineEnd)),
+            's' => self.execute(count, Command::Operate('c', Motion::Right)),
+            'S' => self.execute(count, Command::OperateLine('c')),
+            'Y' => self.execute(count, Command::OperateLine('y')),
+            'J' => {
+                self.save_undo();
+                for _ in 0..n.max(2) - 1 {
+                    if self.row + 1 >= self.lines.len() {
+                        break;
+                    }
+                    let next = self.lines.remove(self.row + 1);
+                    let next: Vec<char> = next.into_iter().skip_while(|c| *c == ' ').collect();
+                    let line = &mut self.lines[self.row];
+                    self.col = line.len();
+                    if !next.is_empty() && line.last().is_some_and(|c| *c != ' ') {
+                        line.push(' ');
+                    }
+                    line.extend(next);
+                }
+            }
+            'p' | 'P' => {
+                if let Some(register) = self.register.clone() {
+                    self.save_undo();
+                    for _ in 0..n {
+                        self.paste(&register, c == 'p');
+                    }
+                }
+            }
+            'u' => {
+                if let Some((lines, (row, col))) = self.undo.pop() {
+                    self.lines = lines;
+                    self.row = row;
+                    self.col = col;
+                }
+            }
+            '~' => {
+                self.save_undo();
+                let line = &mut self.lines[self.row];
+                for _ in 0..n {
+                    if let Some(ch) = line.get_mut(self.col) {
+                        *ch = if ch.is_uppercase() {
+                            ch.to_lowercase().next().unwrap_or(*ch)
+                        } else {
+                            ch.to_uppercase().next().unwrap_or(*ch)
+                        };
+                        self.col += 1;
+                    }
+                }
+            }
+            'i' | 'a' | 'I' | 'A' | 'o' | 'O' => {
+                self.save_undo();
+                match c {
+                    'a' if !self.lines[self.row].is_empty() => self.col += 1,
+                    'I' => self.col = self.first_non_blank(self.row),
+                    'A' => self.col = self.lines[self.row].len(),
+                    'o' => {
+                        self.row += 1;
+                        self.lines.insert(self.row, Vec::new());
+                        self.col = 0;
+                    }
+                    'O' => {
+                        self.lines.insert(self.row, Vec::new());
+                        self.col = 0;
+                    }
+                    _ => {}
+                }
+                self.mode = VimMode::Insert;
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_operator(&mut self, op: char, target: Target) {
+        if op != 'y' {
+            self.save_undo();
+        }
+
+        if target.linewise {
+            let (first, last) = (self.row.min(target.row), self.row.max(target.row));
+            let text = self.lines[first..=last]
+                .iter()
+                .map(|l| l.iter().collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n");
+            self.register = Some(Register {
+                text,
+                linewise: true,
+            });
+
+            match op {
+                'd' => {
+                    self.lines.drain(first..=last);
+                    if self.lines.is_empty() {
+                        self.lines.push(Vec::new());
+                    }
+                    self.row = first.min(self.lines.len() - 1);
+                    self.col = self.first_non_blank(self.row);
+                }
+                'c' => {
+                    self.lines.drain(first + 1..=last);
+                    self.lines[first].clear();
+                    self.row = first;
+                    self.col = 0;
+                    self.mode = VimMode::Insert;
+                }
+                _ => self.row = first,
+            }
+            return;
+        }
+
+        let cursor = (self.row, self.col);
+        let other = (target.row, target.col);
+        let (start, mut end) = if other < cursor {
+            (other, cursor)
+        } else {
+            (cursor, other)
+        };
+        if target.inclusive {
+            end.1 += 1;
+        }
+        end.1 = end.1.min(self.lines[end.0].len());
+
+        let text = self.range_text(start, end);
+        self.register = Some(Register {
+            text,
+            linewise: false,
+        });
+
+        self.row = start.0;
+        self.col = start.1;
+        match op {
+            'd' => self.delete_range(start, end),
+            'c' => {
+                self.delete_range(start, end);
+                self.mode = VimMode::Insert;
+            }
+            _ => {}
+        }
+    }
+
+    fn paste(&mut self, register: &Register, after: bool) {
+        if register.linewise {
+            let at = if after { self.row + 1 } else { self.row };
+            for (i, line) in register.text.split('\n').enumerate() {
+                self.lines.insert(at + i, line.chars().collect());
+            }
+            self.row = at;
+            self.col = self.first_non_blank(at);
+            return;
+        }
+
+        let mut col = self.col;
+        if after && !self.lines[self.row].is_empty() {
+            col += 1;
+        }
+        let mut pieces = register.text.split('\n');
+        let tail = self.lines[self.row].split_off(col);
+        self.lines[self.row].extend(pieces.next().unwrap_or("").chars());
+        let mut row = self.row;
+        for piece in pieces {
+            row += 1;
+            self.lines.insert(row, piece.chars().collect());
+        }
+        self.row = row;
+        self.col = self.lines[row].len().saturating_sub(1);
+        self.lines[row].extend(tail);
+    }
+
+    fn range_text(&self, start: Pos, end: Pos) -> String {
+        if start.0 == end.0 {
+            return self.lines[start.0][start.1..end.1].iter().collect();
+        }
+        let mut text: String = self.lines[start.0][start.1..].iter().collect();
+        for line in &self.lines[start.0 + 1..end.0] {
+            text.push('\n');
+            text.extend(line.iter());
+        }
+        text.push('\n');
+        text.extend(self.lines[end.0][..end.1].iter());
+        text
+    }
+
+    fn delete_range(&mut self, start: Pos, end: Pos) {
+        if start.0 == end.0 {
+            self.lines[start.0].drain(start.1..end.1);
+            return;
+        }
+        let tail: Vec<char> = self.lines[end.0][end.1..].to_vec();
+        self.lines.drain(start.0 + 1..=end.0);
+        self.lines[start.0].truncate(start.1);
+        self.lines[start.0].extend(tail);
+    }
+
+    fn save_undo(&mut self) {
+        self.undo.push((self.lines.clone(), (self.row, self.col)));
+    }
+
+    fn clamp_cursor(&mut self) {
+        self.row = self.row.min(self.lines.len() - 1);
+        self.col = self.col.min(self.lines[self.row].len().saturating_sub(1));
+    }
+
+    fn char_at(&self, row: usize, col: usize) -> char {
+        self.lines[row].get(col).copied().unwrap_or('\n')
+    }
+
+    fn first_non_blank(&self, row: usize) -> usize {
+        self.lines[row]
+            .iter()
+            .position(|c| !c.is_whitespace())
+            .unwrap_or(0)
+    }
+
+    // Every character plus a virtual newline at the end of each line, so word
+    // motions can cross line boundaries
+    fn positions(&self) -> Vec<(Pos, char)> {
+        let mut positions = Vec::new();
+        for (row, line) in self.lines.iter().enumerate() {
+            for (col, c) in line.iter().enumerate() {
+                positions.push(((row, col), *c));
+            }
+            positions.push(((row, line.len()), '\n'));
+        }
+        positions
+    }
+
+    fn motion_target(
+        &mut self,
+        motion: Motion,
+        count: Option<usize>,
+        for_operator: bool,
+    ) -> Option<Target> {
+        let n = count.unwrap_or(1);
+        let len = self.lines[self.row].len();
+        let last_row = self.lines.len() - 1;
+        let charwise = |row, col, inclusive| {
+            Some(Target {
+                row,
+                col,
+                linewise: false,
+                inclusive,
+            })
+        };
+        let linewise = |row, col| {
+            Some(Target {
+                row,
+                col,
+                linewise: true,
+                inclusive: true,
+            })
+        };
+
+        match motion {
+            Motion::Left => charwise(self.row, self.col.saturating_sub(n), false),
+            Motion::Right => {
+                let max = if for_operator {
+                    len
+                } else {
+                    len.saturating_sub(1)
+                };
+                charwise(self.row, (self.col + n).min(max), false)
+            }
+            Motion::Down => linewise((self.row + n).min(last_row), self.col),
+            Motion::Up => linewise(self.row.saturating_sub(n), self.col),
+            Motion::NextLine => {
+                let row = (self.row + n).min(last_row);
+                linewise(row, self.first_non_blank(row))
+            }
+            Motion::LineStart => charwise(self.row, 0, false),
+            Motion::FirstNonBlank => charwise(self.row, self.first_non_blank(self.row), false),
+            Motion::LineEnd => {
+                let row = (self.row + n - 1).min(last_row);
+                charwise(row, self.lines[row].len().saturating_sub(1), true)
+            }
+            Motion::FirstLine | Motion::LastLine => {
+                let row = match (motion, count) {
+                    (_, Some(line)) => line.saturating_sub(1).min(last_row),
+                    (Motion::FirstLine, None) => 0,
+                    _ => last_row,
+                };
+                linewise(row, self.first_non_blank(row))
+            }
+            Motion::Find(kind, target) => {
+                self.last_find = Some((kind, target));
+                self.find_in_line(kind, target, n)
+            }
+            Motion::RepeatFind(reverse) => {
+                let (kind, target) = self.last_find?;
+                let kind = if reverse {
+                    match kind {
+                        'f' => 'F',
+                        'F' => 'f',
+                        't' => 'T',
+                        _ => 't',
+                    }
+                } else {
+                    kind
+                };
+                self.find_in_line(kind, target, n)
+            }
+            Motion::WordStart(big) => {
+                let mut pos = (self.row, self.col);
+                for _ in 0..n {
+                    pos = self.next_word_start(pos, big);
+                }
+                // dw on the last word of a line stops at the end of that line
+                if for_operator && pos.0 > self.row {
+                    pos = (self.row, len);
+                }
+                charwise(pos.0, pos.1, false)
+            }
+            Motion::WordEnd(big) => {
+                let mut pos = (self.row, self.col);
+                for _ in 0..n {
+                    pos = self.next_word_end(pos, big);
+                }
+                charwise(pos.0, pos.1, true)
+            }
+            Motion::WordBack(big) => {
+                let mut pos = (self.row, self.col);
+                for _ in 0..n {
+                    pos = self.prev_word_start(pos, big);
+                }
+                charwise(pos.0, pos.1, false)
+            }
+            Motion::ParagraphNext | Motion::ParagraphPrev => {
+                let mut row = self.row;
+                for _ in 0..n {
+                    row = if motion == Motion::ParagraphNext {
+                        (row + 1..=last_row)
+                            .find(|r| self.lines[*r].is_empty())
+                            .unwrap_or(last_row)
+                    } else {
+                        (0..row)
+                            .rev()
+                            .find(|r| self.lines[*r].is_empty())
+                            .unwrap_or(0)
+                    };
+                }
+                charwise(row, 0, false)
+            }
+        }
+    }
+
+    fn find_in_line(&self, kind: char, target: char, count: usize) -> Option<Target> {
+        let line = &self.lines[self.row];
+        let mut col = self.col;
+        for i in 0..count {
+            // Repeating t/T must not get stuck right before the same match
+            let skip = usize::from(i == 0 && matches!(kind, 't' | 'T'));
+            col = match kind {
+                'f' | 't' => (col + 1 + skip..line.len()).find(|c| line[*c] == target)?,
+                _ => (0..col.saturating_sub(skip))
+                    .rev()
+                    .find(|c| line[*c] == target)?,
+            };
+        }
+        let col = match kind {
+            't' => col - 1,
+            'T' => col + 1,
+            _ => col,
+        };
+        Some(Target {
+            row: self.row,
+            col,
+            linewise: false,
+            inclusive: matches!(kind, 'f' | 't'),
+        })
+    }
+
+    fn next_word_start(&self, from: Pos, big: bool) -> Pos {
+        let positions = self.positions();
+        let Some(mut i) = positions.iter().position(|(p, _)| *p == from) else {
+            return from;
+        };
+        let class = char_class(positions[i].1, big);
+        if class != 0 {
+            while i < positions.len() && char_class(positions[i].1, big) == class {
+                i += 1;
+            }
+        } else {
+            i += 1;
+        }
+        while i < positions.len() {
+            let ((row, col), c) = positions[i];
+            let empty_line = col == 0 && self.lines[row].is_empty();
+            if char_class(c, big) != 0 || empty_line {
+                return (row, col);
+            }
+            i += 1;
+        }
+        let last = self.lines.len() - 1;
+        (last, self.lines[last].len().saturating_sub(1))
+    }
+
+    fn next_word_end(&self, from: Pos, big: bool) -> Pos {
+        let positions = self.positions();
+        let Some(mut i) = positions.iter().position(|(p, _)| *p == from) else {
+            return from;
+        };
+        i += 1;
+        while i < positions.len() && char_class(positions[i].1, big) == 0 {
+            i += 1;
+        }
+        if i >= positions.len() {
+            return from;
+        }
+        let class = char_class(positions[i].1, big);
+        while i + 1 < positions.len() && char_class(positions[i + 1].1, big) == class {
+            i += 1;
+        }
+        positions[i].0
+    }
+
+    fn prev_word_start(&self, from: Pos, big: bool) -> Pos {
+        let positions = self.positions();
+        let Some(mut i) = positions.iter().position(|(p, _)| *p == from) else {
+            return from;
+        };
+        if i == 0 {
+            return from;
+        }
+        i -= 1;
+        while i > 0 && char_class(positions[i].1, big) == 0 {
+            let ((row, col), _) = positions[i];
+            if col == 0 && self.lines[row].is_empty() {
+                return (row, col);
+            }
+            i -= 1;
+        }
+        let class = char_class(positions[i].1, big);
+        while i > 0 && char_class(positions[i - 1].1, big) == class {
+            i -= 1;
+        }
+        positions[i].0
+    }
+
+    // Returns the [start, end) range of iw/aw, quotes and bracket pairs
+    fn text_object(&self, around: bool, object: char) -> Option<(Pos, Pos)> {
+        let line = &self.lines[self.row];
+        match object {
+            'w' | 'W' => {
+                if line.is_empty() {
+                    return None;
+                }
+                let big = object == 'W';
+                let class = char_class(line[self.col], big);
+                let mut start = self.col;
+                while start > 0 && char_class(line[start - 1], big) == class {
+                    start -= 1;
+                }
+                let mut end = self.col + 1;
+                while end < line.len() && char_class(line[end], big) == class {
+                    end += 1;
+                }
+                if around {
+                    let trailing = line[end..].iter().take_while(|c| **c == ' ').count();
+                    if trailing > 0 {
+                        end += trailing;
+                    } else {
+                        start -= line[..start]
+                            .iter()
+                            .rev()
+                            .take_while(|c| **c == ' ')
+                            .count();
+                    }
+                }
+                Some(((self.row, start), (self.row, end)))
+            }
+            // The pair around the cursor, or the next one on the line
+            '"' | '\'' | '`' => {
+                let quotes: Vec<usize> = (0..line.len()).filter(|i| line[*i] == object).collect();
+                let (open, close) = quotes
+                    .chunks(2)
+                    .filter(|pair| pair.len() == 2)
+                    .map(|pair| (pair[0], pair[1]))
+                    .find(|(_, close)| self.col <= *close)?;
+                if around {
+                    let trailing = line[close + 1..].iter().take_while(|c| **c == ' ').count();
+                    Some(((self.row, open), (self.row, close + 1 + trailing)))
+                } else {
+                    Some(((self.row, open + 1), (self.row, close)))
+                }
+            }
+            '(' | ')' | 'b' => self.bracket_object(around, '(', ')'),
+            '{' | '}' | 'B' => self.bracket_object(around, '{', '}'),
+            '[' | ']' => self.bracket_object(around, '[', ']'),
+            '<' | '>' => self.bracket_object(around, '<', '>'),
+            _ => None,
+        }
+    }
+
+    fn bracket_object(&self, around: bool, open: char, close: char) -> Option<(Pos, Pos)> {
+        let positions = self.positions();
+        let cursor = positions
+            .iter()
+            .position(|(p, _)| *p == (self.row, self.col))?;
+
+        let mut depth = 0;
+        let mut start = None;
+        for i in (0..=cursor).rev() {
+            let c = positions[i].1;
+            if c == close && i != cursor {
+                depth += 1;
+            } else if c == open {
+                if depth == 0 {
+                    start = Some(i);
+                    break;
+                }
+                depth -= 1;
+            }
+        }
+        let start = start?;
+
+        depth = 0;
+        let mut end = None;
+        for (i, (_, c)) in positions.iter().enumerate().skip(start + 1) {
+            if *c == open {
+                depth += 1;
+            } else if *c == close {
+                if depth == 0 {
+                    end = Some(i);
+                    break;
+                }
+                depth -= 1;
+            }
+        }
+        let end = end?;
+
+        if around {
+            let (row, col) = positions[end].0;
+            Some((positions[start].0, (row, col + 1)))
+        } else {
+            Some((positions[start + 1].0, positions[end].0))
+        }
+    }
+}
+
+// 0 = blank, 1 = keyword characters, 2 = punctuation; WORDs only split on blanks
+fn char_class(c: char, big: bool) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if big || c.is_alphanumeric() || c == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+fn take_count(keys: &[char]) -> (Option<usize>, &[char]) {
+    let digits = keys
+        .iter()
+        .enumerate()
+        .take_while(|(i, c)| c.is_ascii_digit() && (*i > 0 || **c != '0'))
+        .count();
+    if digits == 0 {
+        return (None, keys);
+    }
+    let count: String = keys[..digits].iter().collect();
+    (
+        count.parse().ok().map(|c: usize| c.min(999)),
+        &keys[digits..],
+    )
+}
+
+fn parse(keys: &[char]) -> Parsed<(Option<usize>, Command)> {
+    let (count, rest) = take_count(keys);
+    let Some(&first) = rest.first() else {
+        return Parsed::Incomplete;
+    };
+
+    if matches!(first, 'd' | 'c' | 'y') {
+        let (inner, rest) = take_count(&rest[1..]);
+        let count = match (count, inner) {
+            (Some(a), Some(b)) => Some(a * b),
+            (a, b) => a.or(b),
+        };
+        return match rest {
+            [] => Parsed::Incomplete,
+            [c] if *c == first => Parsed::Done((count, Command::OperateLine(first))),
+            ['i' | 'a'] => Parsed::Incomplete,
+            [kind @ ('i' | 'a'), object] => {
+                Parsed::Done((count, Command::OperateObject(first, *kind == 'a', *object)))
+            }
+            _ => match parse_motion(rest) {
+                Parsed::Done(motion) => Parsed::Done((count, Command::Operate(first, motion))),
+                Parsed::Incomplete => Parsed::Incomplete,
+                Parsed::Invalid => Parsed::Invalid,
+            },
+        };
+    }
+
+    match parse_motion(rest) {
+        Parsed::Done(motion) => Parsed::Done((count, Command::Move(motion))),
+        Parsed::Incomplete => Parsed::Incomplete,
+        Parsed::Invalid => match rest {
+            ['r'] => Parsed::Incomplete,
+            ['r', c] => Parsed::Done((count, Command::Replace(*c))),
+            [c] if "xXDCsSYJpPu~iaIAoO".contains(*c) => Parsed::Done((count, Command::Simple(*c))),
+            _ => Parsed::Invalid,
+        },
+    }
+}
+
+fn parse_motion(keys: &[char]) -> Parsed<Motion> {
+    let motion = match keys {
+        ['h'] => Motion::Left,
+        ['l' | ' '] => Motion::Right,
+        ['j'] => Motion::Down,
+        ['k'] => Motion::Up,
+        ['+'] => Motion::NextLine,
+        ['w'] => Motion::WordStart(false),
+        ['W'] => Motion::WordStart(true),
+        ['e'] => Motion::WordEnd(false),
+        ['E'] => Motion::WordEnd(true),
+        ['b'] => Motion::WordBack(false),
+        ['B'] => Motion::WordBack(true),
+        ['0'] => Motion::LineStart,
+        ['^'] => Motion::FirstNonBlank,
+        ['$'] => Motion::LineEnd,
+        ['G'] => Motion::LastLine,
+        ['g'] => return Parsed::Incomplete,
+        ['g', 'g'] => Motion::FirstLine,
+        ['f' | 'F' | 't' | 'T'] => return Parsed::Incomplete,
+        [kind @ ('f' | 'F' | 't' | 'T'), c] => Motion::Find(*kind, *c),
+        [';'] => Motion::RepeatFind(false),
+        [','] => Motion::RepeatFind(true),
+        ['}'] => Motion::ParagraphNext,
+        ['{'] => Motion::ParagraphPrev,
+        _ => return Parsed::Invalid,
+    };
+    Parsed::Done(motion)
+}
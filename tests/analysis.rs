@@ -0,0 +1,147 @@
+use lazydvorak::analysis::{LayoutAnalysis, analyze, format_report, read_corpus};
+use lazydvorak::layout::{Finger, KeyboardLayout};
+use std::fs;
+
+// Small enough to work out by hand: spaces split it into the bigrams
+// th he | ca at | de ec
+const CORPUS: &str = "the cat dec";
+
+fn run(layout: &str, text: &str) -> LayoutAnalysis {
+    let layout = KeyboardLayout::find_builtin(layout).expect("built-in layout");
+    analyze(&layout, &[text.to_string()])
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 0.01,
+        "expected {:.2}, got {:.2}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn qwerty_metrics() {
+    let result = run("qwerty", CORPUS);
+    assert_eq!(result.keystrokes, 9);
+    assert_eq!(result.unmapped, 0);
+    assert_eq!(result.bigrams, 6);
+
+    // h a d
+    assert_eq!(result.home_row_keystrokes, 3);
+    assert_close(result.home_row_rate(), 100.0 / 3.0);
+    // th he
+    assert_eq!(result.alternations, 2);
+    assert_close(result.alternation_rate(), 100.0 / 3.0);
+    // de ec, both on the left middle finger
+    assert_eq!(result.same_finger_bigrams, 2);
+    assert_close(result.same_finger_rate(), 100.0 / 3.0);
+    // ec goes from the top row to the bottom row
+    assert_eq!(result.row_jumps, 1);
+    assert_close(result.row_jump_rate(), 100.0 / 6.0);
+
+    assert_close(result.finger_load(Finger::LeftMiddle), 500.0 / 9.0);
+    assert_close(result.finger_load(Finger::LeftIndex), 200.0 / 9.0);
+    assert_close(result.finger_load(Finger::LeftPinky), 100.0 / 9.0);
+    assert_close(result.finger_load(Finger::RightIndex), 100.0 / 9.0);
+    assert_close(result.finger_load(Finger::RightPinky), 0.0);
+}
+
+#[test]
+fn dvorak_metrics() {
+    let result = run("dvorak", CORPUS);
+    assert_eq!(result.keystrokes, 9);
+    assert_eq!(result.bigrams, 6);
+
+    // Everything but the two c keys is on the home row
+    assert_eq!(result.home_row_keystrokes, 7);
+    assert_close(result.home_row_rate(), 700.0 / 9.0);
+    // Only th stays on one hand
+    assert_eq!(result.alternations, 5);
+    assert_close(result.alternation_rate(), 500.0 / 6.0);
+    assert_eq!(result.same_finger_bigrams, 0);
+    assert_eq!(result.row_jumps, 0);
+
+    assert_close(result.finger_load(Finger::RightMiddle), 400.0 / 9.0);
+    assert_close(result.finger_load(Finger::RightIndex), 200.0 / 9.0);
+    assert_close(result.finger_load(Finger::LeftMiddle), 200.0 / 9.0);
+    assert_close(result.finger_load(Finger::LeftPinky), 100.0 / 9.0);
+}
+
+#[test]
+fn repeated_keys_are_not_same_finger_bigrams() {
+    let result = run("qwerty", "ll");
+    assert_eq!(result.bigrams, 1);
+    assert_eq!(result.same_finger_bigrams, 0);
+}
+
+#[test]
+fn shifted_characters_count_on_their_key() {
+    let result = run("qwerty", "A!");
+    assert_eq!(result.keystrokes, 2);
+    assert_eq!(result.finger_load(Finger::LeftPinky), 100.0);
+}
+
+#[test]
+fn unmapped_characters_are_counted_and_break_bigrams() {
+    let result = run("qwerty", "año 😀 x");
+    assert_eq!(result.keystrokes, 3);
+    assert_eq!(result.unmapped, 2);
+    assert_eq!(result.bigrams, 0);
+
+    // Whitespace is neither a keystroke nor unmapped, spaces are counted apart
+    let result = run("qwerty", " \t\n ");
+    assert_eq!(result.keystrokes, 0);
+    assert_eq!(result.unmapped, 0);
+    assert_eq!(result.spaces, 2);
+    assert_eq!(result.alternation_rate(), 0.0);
+}
+
+#[test]
+fn layouts_map_the_same_ascii_symbols() {
+    let code = "#[derive(Debug)] fn main() { let x = a[0] + b * 2 - c / 3 % 4; println!(\"{x}\"); } // `~^|&@$<>?:'_\\";
+    assert_eq!(run("qwerty", code).unmapped, 0);
+    assert_eq!(run("dvorak", code).unmapped, 0);
+}
+
+#[test]
+fn report_has_a_column_per_layout() {
+    let results = vec![run("dvorak", CORPUS), run("qwerty", CORPUS)];
+    let report = format_report(&results);
+    let header = report.lines().next().unwrap();
+    assert!(header.contains("Dvorak Programmer"));
+    assert!(header.contains("QWERTY"));
+    assert!(report.contains("Same-finger bigrams"));
+    assert!(report.contains("Spaces (thumbs)"));
+    assert!(report.contains("Finger load (no Space)"));
+    assert!(!report.contains("Left thumb"));
+}
+
+#[test]
+fn corpus_has_no_size_limit_and_reports_what_it_skips() {
+    let dir = std::env::temp_dir().join(format!("lazydvorak-corpus-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::write(dir.join("big.txt"), "x".repeat(100 * 1024)).unwrap();
+    fs::write(dir.join("binary.bin"), [0xff, 0xfe, 0x00]).unwrap();
+    fs::write(dir.join(".hidden"), "hidden").unwrap();
+    fs::write(dir.join("target/out.txt"), "build output").unwrap();
+
+    let corpus = read_corpus(std::slice::from_ref(&dir)).unwrap();
+    assert_eq!(corpus.texts.len(), 1);
+    assert_eq!(corpus.texts[0].len(), 100 * 1024);
+    let skipped: Vec<_> = corpus
+        .skipped
+        .iter()
+        .map(|(path, reason)| (path.file_name().unwrap().to_str().unwrap(), *reason))
+        .collect();
+    assert_eq!(
+        skipped,
+        [
+            (".hidden", "hidden"),
+            ("binary.bin", "not UTF-8 text"),
+            ("target", "build or dependency output"),
+        ]
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,39 @@
+// Shared by every test binary, each of which only uses some of the helpers
+#![allow(dead_code)]
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use lazydvorak::app::App;
+use lazydvorak::history::History;
+use lazydvorak::layout::KeyboardLayout;
+use lazydvorak::types::AppMode;
+
+// Every test App keeps its history in memory so finished sessions never reach
+// the real history file
+pub fn app() -> App {
+    let layout = KeyboardLayout::builtin().remove(0);
+    App::with_history("en".to_string(), layout, History::default())
+}
+
+pub fn press(app: &mut App, code: KeyCode) {
+    app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+}
+
+pub fn type_str(app: &mut App, text: &str) {
+    for c in text.chars() {
+        let code = match c {
+            '\n' => KeyCode::Enter,
+            '\t' => KeyCode::Tab,
+            _ => KeyCode::Char(c),
+        };
+        press(app, code);
+    }
+}
+
+// A practice session over a single exercise, as if loaded with --file
+pub fn custom(exercise: &str) -> App {
+    let mut app = app();
+    app.mode = AppMode::CustomFile;
+    app.custom_exercises = vec![exercise.to_string()];
+    app.start_exercise();
+    app
+}
@@ -0,0 +1,84 @@
+use lazydvorak::exercises::custom::split_exercises;
+use lazydvorak::exercises::{SplitMode, load_exercises_from_dir, load_exercises_from_file};
+use std::fs;
+use std::path::PathBuf;
+
+// A fresh directory per test so parallel tests do not see each other's files
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("lazydvorak-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn lines_mode_keeps_non_empty_trimmed_lines() {
+    assert_eq!(
+        split_exercises("  first line \r\n\n\tsecond\n   \nthird", SplitMode::Lines),
+        ["first line", "second", "third"]
+    );
+    assert!(split_exercises("\n \n", SplitMode::Lines).is_empty());
+}
+
+#[test]
+fn blocks_mode_splits_on_blank_lines() {
+    let content = "\n\nfn a() {\n    1\n}  \n\nfn b() {}\n\n\n\nlast\n";
+    assert_eq!(
+        split_exercises(content, SplitMode::Blocks),
+        ["fn a() {\n    1\n}", "fn b() {}", "last"]
+    );
+}
+
+#[test]
+fn file_mode_keeps_indentation_and_drops_trailing_space() {
+    let content = "\r\nfn main() {   \r\n    println!();\r\n}\r\n\r\n";
+    assert_eq!(
+        split_exercises(content, SplitMode::File),
+        ["fn main() {\n    println!();\n}"]
+    );
+    assert!(split_exercises(" \n\t\n", SplitMode::File).is_empty());
+}
+
+#[test]
+fn split_modes_parse_from_flags() {
+    assert_eq!(SplitMode::parse("auto"), Some(SplitMode::Auto));
+    assert_eq!(SplitMode::parse("lines"), Some(SplitMode::Lines));
+    assert_eq!(SplitMode::parse("blocks"), Some(SplitMode::Blocks));
+    assert_eq!(SplitMode::parse("file"), Some(SplitMode::File));
+    assert_eq!(SplitMode::parse("words"), None);
+}
+
+#[test]
+fn auto_mode_picks_a_split_from_the_extension() {
+    let dir = scratch_dir("auto");
+    let content = "one\ntwo\n\nthree";
+    for name in ["list.txt", "notes", "doc.md", "code.rs"] {
+        fs::write(dir.join(name), content).unwrap();
+    }
+    let load = |name: &str| load_exercises_from_file(&dir.join(name), SplitMode::Auto).unwrap();
+    assert_eq!(load("list.txt"), ["one", "two", "three"]);
+    assert_eq!(load("notes"), ["one", "two", "three"]);
+    assert_eq!(load("doc.md"), ["one\ntwo", "three"]);
+    assert_eq!(load("code.rs"), [content]);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn directories_are_walked_in_order_skipping_noise() {
+    let dir = scratch_dir("walk");
+    fs::create_dir_all(dir.join("src/nested")).unwrap();
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::create_dir_all(dir.join(".git")).unwrap();
+    fs::write(dir.join("b.txt"), "b").unwrap();
+    fs::write(dir.join("a.txt"), "a").unwrap();
+    fs::write(dir.join("src/nested/c.txt"), "c").unwrap();
+    fs::write(dir.join(".hidden.txt"), "hidden").unwrap();
+    fs::write(dir.join("target/out.txt"), "build output").unwrap();
+    fs::write(dir.join(".git/HEAD"), "ref").unwrap();
+    fs::write(dir.join("binary.txt"), [0xff, 0xfe, 0x00]).unwrap();
+    fs::write(dir.join("huge.txt"), "x".repeat(65 * 1024)).unwrap();
+
+    let exercises = load_exercises_from_dir(&dir, SplitMode::Auto).unwrap();
+    assert_eq!(exercises, ["a", "b", "c"]);
+    fs::remove_dir_all(&dir).unwrap();
+}
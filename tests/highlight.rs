@@ -0,0 +1,141 @@
+use lazydvorak::exercises::{TokenKind, highlight_code};
+
+// Kind of the first occurrence of `needle`, which must be a single token kind
+fn kind_of(code: &str, lang: &str, needle: &str) -> TokenKind {
+    let kinds = highlight_code(code, lang).expect("known language");
+    let byte = code.find(needle).expect("needle in code");
+    let start = code[..byte].chars().count();
+    let len = needle.chars().count();
+    let slice = &kinds[start..start + len];
+    assert!(
+        slice.iter().all(|k| *k == slice[0]),
+        "{:?} in {:?} has mixed kinds {:?}",
+        needle,
+        code,
+        slice
+    );
+    slice[0]
+}
+
+#[test]
+fn one_kind_per_char_and_unknown_languages() {
+    let code = "let s = \"日本\";";
+    assert_eq!(
+        highlight_code(code, "rust").unwrap().len(),
+        code.chars().count()
+    );
+    assert_eq!(highlight_code(code, "cobol"), None);
+    assert_eq!(highlight_code("", "rust"), Some(vec![]));
+}
+
+#[test]
+fn rust() {
+    let code = "fn main() {\n    let x = 42; // answer\n    let s = \"a \\\"b\\\" c\";\n    /* block\n comment */ let c = 'z';\n    for i in 1..n { print!(\"{}\", 3.5e2); }\n}";
+    assert_eq!(kind_of(code, "rust", "fn"), TokenKind::Keyword);
+    assert_eq!(kind_of(code, "rust", "let"), TokenKind::Keyword);
+    assert_eq!(kind_of(code, "rust", "main"), TokenKind::Function);
+    assert_eq!(kind_of(code, "rust", "print"), TokenKind::Function);
+    assert_eq!(kind_of(code, "rust", "42"), TokenKind::Number);
+    assert_eq!(kind_of(code, "rust", "3.5e2"), TokenKind::Number);
+    assert_eq!(kind_of(code, "rust", "// answer"), TokenKind::Comment);
+    assert_eq!(
+        kind_of(code, "rust", "/* block\n comment */"),
+        TokenKind::Comment
+    );
+    assert_eq!(
+        kind_of(code, "rust", "\"a \\\"b\\\" c\""),
+        TokenKind::String
+    );
+    assert_eq!(kind_of(code, "rust", "'z'"), TokenKind::String);
+    assert_eq!(kind_of(code, "rust", "{"), TokenKind::Punctuation);
+
+    // 1..n is a number, a range operator and a name
+    assert_eq!(kind_of(code, "rust", "1"), TokenKind::Number);
+    assert_eq!(kind_of(code, "rust", ".."), TokenKind::Punctuation);
+    assert_eq!(kind_of(code, "rust", "x "), TokenKind::Plain);
+}
+
+#[test]
+fn rust_lifetimes_and_escaped_chars() {
+    let code = "fn f<'a>(s: &'a str) -> char { '\\n' }";
+    assert_eq!(kind_of(code, "rust", "'a"), TokenKind::Keyword);
+    assert_eq!(kind_of(code, "rust", "'\\n'"), TokenKind::String);
+}
+
+#[test]
+fn unterminated_strings_stop_at_the_line_end() {
+    for lang in ["rust", "typescript", "python", "ruby", "lua"] {
+        let code = "x = \"open\ny = 1";
+        assert_eq!(kind_of(code, lang, "\"open"), TokenKind::String, "{}", lang);
+        assert_eq!(kind_of(code, lang, "1"), TokenKind::Number, "{}", lang);
+    }
+
+    // A string left open at the end of the snippet does not overrun it
+    let kinds = highlight_code("s = 'abc\\", "python").unwrap();
+    assert_eq!(kinds.len(), 9);
+    assert_eq!(kinds[8], TokenKind::String);
+}
+
+#[test]
+fn unterminated_block_comment_runs_to_the_end() {
+    let code = "let a = 1; /* never closed\nlet b = 2;";
+    assert_eq!(
+        kind_of(code, "rust", "/* never closed\nlet b = 2;"),
+        TokenKind::Comment
+    );
+}
+
+#[test]
+fn typescript() {
+    let code = "const greet = (name: string): void => {\n  console.log(`hi ${name}`, 'x\\'y', 0xff); // log\n};";
+    assert_eq!(kind_of(code, "typescript", "const"), TokenKind::Keyword);
+    assert_eq!(kind_of(code, "typescript", "log"), TokenKind::Function);
+    assert_eq!(
+        kind_of(code, "typescript", "`hi ${name}`"),
+        TokenKind::String
+    );
+    assert_eq!(kind_of(code, "typescript", "'x\\'y'"), TokenKind::String);
+    assert_eq!(kind_of(code, "typescript", "0xff"), TokenKind::Number);
+    assert_eq!(kind_of(code, "typescript", "// log"), TokenKind::Comment);
+}
+
+#[test]
+fn python() {
+    let code =
+        "def area(r):\n    # circle\n    return 3.14 * r ** 2  # done\nprint('it\\'s', \"ok\")";
+    assert_eq!(kind_of(code, "python", "def"), TokenKind::Keyword);
+    assert_eq!(kind_of(code, "python", "return"), TokenKind::Keyword);
+    assert_eq!(kind_of(code, "python", "area"), TokenKind::Function);
+    assert_eq!(kind_of(code, "python", "# circle"), TokenKind::Comment);
+    assert_eq!(kind_of(code, "python", "3.14"), TokenKind::Number);
+    assert_eq!(kind_of(code, "python", "'it\\'s'"), TokenKind::String);
+    assert_eq!(kind_of(code, "python", "\"ok\""), TokenKind::String);
+}
+
+#[test]
+fn ruby() {
+    let code = "def greet(name)\n  puts \"hi #{name}\" # say hi\n  return 42\nend";
+    assert_eq!(kind_of(code, "ruby", "def"), TokenKind::Keyword);
+    assert_eq!(kind_of(code, "ruby", "end"), TokenKind::Keyword);
+    assert_eq!(kind_of(code, "ruby", "greet"), TokenKind::Function);
+    // The interpolation stays inside the string, not a comment
+    assert_eq!(kind_of(code, "ruby", "\"hi #{name}\""), TokenKind::String);
+    assert_eq!(kind_of(code, "ruby", "# say hi"), TokenKind::Comment);
+    assert_eq!(kind_of(code, "ruby", "42"), TokenKind::Number);
+}
+
+#[test]
+fn lua() {
+    let code = "local function add(a, b)\n  -- sum\n  return a + b\nend\n--[[ long\ncomment ]] print(\"x\\\"y\", 10)";
+    assert_eq!(kind_of(code, "lua", "local"), TokenKind::Keyword);
+    assert_eq!(kind_of(code, "lua", "function"), TokenKind::Keyword);
+    assert_eq!(kind_of(code, "lua", "end"), TokenKind::Keyword);
+    assert_eq!(kind_of(code, "lua", "add"), TokenKind::Function);
+    assert_eq!(kind_of(code, "lua", "-- sum"), TokenKind::Comment);
+    assert_eq!(
+        kind_of(code, "lua", "--[[ long\ncomment ]]"),
+        TokenKind::Comment
+    );
+    assert_eq!(kind_of(code, "lua", "\"x\\\"y\""), TokenKind::String);
+    assert_eq!(kind_of(code, "lua", "10"), TokenKind::Number);
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use common::{app, press};
+use crossterm::event::KeyCode;
+use lazydvorak::app::App;
+use lazydvorak::types::AppMode;
+use std::time::{Duration, Instant};
+
+fn type_first_key(app: &mut App) {
+    let c = app.target_text.chars().next().expect("an exercise");
+    press(app, KeyCode::Char(c));
+}
+
+#[test]
+fn finished_sessions_are_kept_in_memory() {
+    let mut app = app();
+    app.mode = AppMode::WordsSimple;
+    app.start_exercise();
+    type_first_key(&mut app);
+    app.stats.start_time = Some(Instant::now() - Duration::from_secs(10));
+    press(&mut app, KeyCode::Esc);
+
+    assert_eq!(app.history.records.len(), 1);
+    let record = &app.history.records[0];
+    assert_eq!(record.mode, AppMode::WordsSimple);
+    assert_eq!(record.correct, 1);
+    assert!(record.duration_secs >= 10.0);
+}
+
+#[test]
+fn empty_and_instant_sessions_are_not_recorded() {
+    let mut app = app();
+    app.mode = AppMode::WordsSimple;
+    app.start_exercise();
+    press(&mut app, KeyCode::Esc);
+    assert!(app.history.records.is_empty());
+
+    app.mode = AppMode::WordsSimple;
+    app.start_exercise();
+    type_first_key(&mut app);
+    press(&mut app, KeyCode::Esc);
+    assert!(app.history.records.is_empty());
+}
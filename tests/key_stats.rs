@@ -0,0 +1,118 @@
+mod common;
+
+use common::{custom, press, type_str};
+use crossterm::event::KeyCode;
+use lazydvorak::history::History;
+use lazydvorak::stats::{KeystrokeRecord, Stats};
+use std::fs;
+use std::time::{Duration, Instant};
+
+fn stroke(expected: char, typed: char, latency_ms: Option<u32>) -> KeystrokeRecord {
+    KeystrokeRecord {
+        expected,
+        typed,
+        latency_ms,
+        error: expected != typed,
+    }
+}
+
+fn stats_from(keystrokes: Vec<KeystrokeRecord>) -> Stats {
+    let mut stats = Stats::new();
+    stats.keystrokes = keystrokes;
+    stats
+}
+
+#[test]
+fn keystrokes_aggregate_by_the_expected_key() {
+    let stats = stats_from(vec![
+        stroke('a', 'a', None),
+        stroke('b', 'x', Some(300)),
+        stroke('a', 's', Some(100)),
+        stroke('a', 'a', Some(200)),
+    ]);
+    let keys = stats.key_stats();
+    assert_eq!(keys.iter().map(|k| k.key).collect::<Vec<_>>(), ['a', 'b']);
+
+    let a = &keys[0];
+    assert_eq!((a.attempts, a.errors), (3, 1));
+    assert!((a.error_rate() - 100.0 / 3.0).abs() < 0.01);
+    // The first key of a session has nothing to be timed against
+    assert_eq!(a.timed_attempts, 2);
+    assert_eq!(a.average_latency_ms(), Some(150.0));
+
+    let b = &keys[1];
+    assert_eq!((b.attempts, b.errors), (1, 1));
+    assert_eq!(b.error_rate(), 100.0);
+    assert_eq!(b.average_latency_ms(), Some(300.0));
+}
+
+#[test]
+fn recorded_keystrokes_are_timed_from_the_previous_one() {
+    let mut stats = Stats::new();
+    stats.record_keystroke('a', 'a');
+    stats.record_keystroke('b', 'c');
+    assert_eq!(stats.keystrokes[0].latency_ms, None);
+    assert!(!stats.keystrokes[0].error);
+    assert!(stats.keystrokes[1].latency_ms.is_some());
+    assert!(stats.keystrokes[1].error);
+}
+
+#[test]
+fn weakest_keys_rank_error_rate_before_latency() {
+    let stats = stats_from(vec![
+        stroke('a', 'x', Some(100)),
+        stroke('a', 'a', Some(100)),
+        stroke('b', 'x', Some(400)),
+        stroke('b', 'b', Some(400)),
+        stroke('c', 'c', Some(900)),
+        stroke('d', 'x', Some(50)),
+    ]);
+    let weakest: Vec<char> = stats.weakest_keys(3).iter().map(|k| k.key).collect();
+    assert_eq!(weakest, ['d', 'b', 'a']);
+    assert_eq!(stats.weakest_keys(10).last().unwrap().key, 'c');
+}
+
+#[test]
+fn history_merges_key_stats_per_layout() {
+    let record = |layout: &str, key: char, attempts: u32, errors: u32, latency: u64| {
+        format!(
+            r#"{{"mode":"WordsSimple","layout":"{}","exercise_count":"All","exercises_completed":1,"wpm":40.0,"accuracy":90.0,"correct":9,"errors":1,"duration_secs":12.0,"timestamp":1,"key_stats":[{{"key":"{}","attempts":{},"errors":{},"total_latency_ms":{},"timed_attempts":{}}}]}}"#,
+            layout, key, attempts, errors, latency, attempts
+        )
+    };
+    let path =
+        std::env::temp_dir().join(format!("lazydvorak-key-stats-{}.jsonl", std::process::id()));
+    let lines = [
+        record("QWERTY", 'a', 4, 1, 400),
+        "not a record".to_string(),
+        record("QWERTY", 'a', 6, 2, 1200),
+        record("Dvorak Programmer", 'a', 10, 10, 100),
+        record("QWERTY", 'e', 2, 0, 100),
+    ];
+    fs::write(&path, lines.join("\n")).unwrap();
+    let history = History::load_from(path.clone());
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(history.records.len(), 4);
+    let keys = history.key_stats("QWERTY");
+    assert_eq!(keys.len(), 2);
+    let a = keys.iter().find(|k| k.key == 'a').unwrap();
+    assert_eq!((a.attempts, a.errors), (10, 3));
+    assert_eq!(a.average_latency_ms(), Some(160.0));
+    assert!(history.key_stats("Colemak").is_empty());
+}
+
+#[test]
+fn history_key_stats_are_refreshed_when_a_session_is_saved() {
+    let mut app = custom("ab");
+    assert!(app.history_key_stats.is_empty());
+
+    // Long enough ago to be kept in the history
+    app.stats.start_time = Some(Instant::now() - Duration::from_secs(5));
+    type_str(&mut app, "a");
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.history.records.len(), 1);
+    assert_eq!(app.history_key_stats.len(), 1);
+    assert_eq!(app.history_key_stats[0].key, 'a');
+    assert_eq!(app.history_key_stats[0].attempts, 1);
+}
@@ -0,0 +1,160 @@
+use lazydvorak::keymaps::Keymap;
+use lazydvorak::keymaps::import::{parse_lua, parse_vimscript};
+
+fn lhs_list(keymaps: &[Keymap]) -> Vec<&str> {
+    keymaps.iter().map(|k| k.lhs.as_str()).collect()
+}
+
+#[test]
+fn lua_keymap_set_with_options() {
+    let keymaps = parse_lua(
+        r#"vim.keymap.set("n", "<leader>ff", "<cmd>Telescope find_files<CR>", { desc = "Find files" })"#,
+    );
+    assert_eq!(keymaps.len(), 1);
+    assert_eq!(keymaps[0].lhs, "<leader>ff");
+    assert_eq!(keymaps[0].mode.as_deref(), Some("n"));
+    assert_eq!(
+        keymaps[0].rhs.as_deref(),
+        Some("<cmd>Telescope find_files<CR>")
+    );
+    assert_eq!(keymaps[0].desc.as_deref(), Some("Find files"));
+}
+
+#[test]
+fn lua_mode_tables_and_function_rhs() {
+    let keymaps = parse_lua(
+        r#"
+        vim.keymap.set({ "n", "v" }, "<leader>y", function()
+            vim.cmd("normal! y", { a = 1, b = 2 })
+        end, { desc = "Yank" })
+        "#,
+    );
+    assert_eq!(keymaps.len(), 1);
+    assert_eq!(keymaps[0].mode.as_deref(), Some("nv"));
+    assert_eq!(keymaps[0].rhs, None);
+    assert_eq!(keymaps[0].desc.as_deref(), Some("Yank"));
+}
+
+#[test]
+fn lua_aliases_wrappers_and_buffer_maps() {
+    let keymaps = parse_lua(
+        r#"
+        local map = vim.keymap.set
+        local function bind(mode, lhs, rhs)
+            vim.keymap.set(mode, lhs, rhs)
+        end
+        map("n", "<C-h>", "<C-w>h")
+        bind("i", "jk", "<Esc>")
+        vim.api.nvim_buf_set_keymap(0, "n", "gd", "<cmd>lua vim.lsp.buf.definition()<CR>", {})
+        "#,
+    );
+    assert_eq!(lhs_list(&keymaps), vec!["<C-h>", "jk", "gd"]);
+    assert_eq!(keymaps[1].mode.as_deref(), Some("i"));
+    assert_eq!(keymaps[2].mode.as_deref(), Some("n"));
+}
+
+#[test]
+fn lua_lazy_keys_specs() {
+    let keymaps = parse_lua(
+        r#"
+        return {
+            "nvim-telescope/telescope.nvim",
+            keys = {
+                "<leader>fb",
+                { "<leader>fg", "<cmd>Telescope live_grep<cr>", desc = "Grep", mode = { "n", "x" } },
+            },
+        }
+        "#,
+    );
+    assert_eq!(lhs_list(&keymaps), vec!["<leader>fb", "<leader>fg"]);
+    assert_eq!(keymaps[0].mode.as_deref(), Some("n"));
+    assert_eq!(keymaps[1].mode.as_deref(), Some("nx"));
+    assert_eq!(keymaps[1].desc.as_deref(), Some("Grep"));
+}
+
+#[test]
+fn lua_comments_and_long_strings_are_ignored() {
+    let keymaps = parse_lua(
+        r#"
+        -- vim.keymap.set("n", "<leader>a", "a")
+        --[[ vim.keymap.set("n", "<leader>b", "b") ]]
+        local s = [[vim.keymap.set("n", "<leader>c", "c")]]
+        vim.keymap.set("n", "<leader>d", "d")
+        "#,
+    );
+    assert_eq!(lhs_list(&keymaps), vec!["<leader>d"]);
+}
+
+#[test]
+fn lua_stray_closer_in_call_terminates() {
+    let keymaps = parse_lua(r#"vim.keymap.set("n", "<leader>x", "y"})"#);
+    assert_eq!(lhs_list(&keymaps), vec!["<leader>x"]);
+
+    let keymaps = parse_lua(r#"vim.keymap.set("n", "<leader>x"]; "y")"#);
+    assert_eq!(lhs_list(&keymaps), vec!["<leader>x"]);
+}
+
+#[test]
+fn lua_stray_closer_in_table_terminates() {
+    let keymaps = parse_lua(r#"local t = { keys = { "<leader>a" ] }"#);
+    assert_eq!(lhs_list(&keymaps), vec!["<leader>a"]);
+
+    let keymaps = parse_lua(r#"vim.keymap.set("n", "<leader>b", "b", { desc = "B" )"#);
+    assert_eq!(keymaps[0].desc.as_deref(), Some("B"));
+}
+
+#[test]
+fn lua_malformed_input_keeps_later_mappings() {
+    let keymaps = parse_lua(
+        r#"
+        vim.keymap.set("n", "<leader>x", "y"}))]
+        keys = { { "<leader>k" ) }
+        vim.keymap.set("n", "<leader>z", "z")
+        "#,
+    );
+    assert!(lhs_list(&keymaps).contains(&"<leader>z"));
+}
+
+#[test]
+fn lua_truncated_files_terminate() {
+    for source in [
+        "vim.keymap.set(",
+        r#"vim.keymap.set("n", "#,
+        r#"vim.keymap.set("n", "<leader>q", function() "#,
+        "keys = {",
+        r#"keys = { { "<leader>w""#,
+        r#"vim.keymap.set("n, "<leader>u", "u")"#,
+        "}])",
+    ] {
+        parse_lua(source);
+    }
+}
+
+#[test]
+fn vimscript_map_commands() {
+    let keymaps = parse_vimscript(
+        r#"
+        nnoremap <silent> <leader>w :w<CR>
+        :vmap <buffer><expr> <C-j> Move()
+        map! <C-e> <End>
+        inoremap jk <Esc>
+        "#,
+    );
+    assert_eq!(
+        lhs_list(&keymaps),
+        vec!["<leader>w", "<C-j>", "<C-e>", "jk"]
+    );
+    assert_eq!(keymaps[0].mode.as_deref(), Some("n"));
+    assert_eq!(keymaps[0].rhs.as_deref(), Some(":w<CR>"));
+    assert_eq!(keymaps[1].mode.as_deref(), Some("v"));
+    assert_eq!(keymaps[2].mode.as_deref(), Some("!"));
+    assert_eq!(keymaps[3].mode.as_deref(), Some("i"));
+}
+
+#[test]
+fn vimscript_malformed_lines_are_skipped() {
+    let keymaps = parse_vimscript(
+        "nnoremap\nnnoremap <silent>\nnmap! x y\nzmap a b\nlet map = 1\nmap(\"x\")\n\u{0}nmap\u{0}",
+    );
+    assert!(keymaps.is_empty());
+}
@@ -0,0 +1,212 @@
+mod common;
+
+use common::{app, press};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use lazydvorak::app::App;
+use lazydvorak::keymaps::{KeyChord, Keymap, parse_leader, parse_notation};
+use lazydvorak::types::AppMode;
+use lazydvorak::ui;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+
+fn parse(lhs: &str) -> Vec<KeyChord> {
+    parse_notation(lhs, &KeyChord::char('\\'))
+}
+
+fn render(chords: &[KeyChord]) -> String {
+    chords.iter().map(|c| c.display()).collect()
+}
+
+#[test]
+fn plain_keys_are_single_chords() {
+    assert_eq!(
+        parse("gcc"),
+        vec![
+            KeyChord::char('g'),
+            KeyChord::char('c'),
+            KeyChord::char('c')
+        ]
+    );
+}
+
+#[test]
+fn leader_expands_to_the_configured_key() {
+    let space = KeyChord::char(' ');
+    assert_eq!(
+        parse_notation("<leader>ff", &space),
+        vec![space.clone(), KeyChord::char('f'), KeyChord::char('f')]
+    );
+    assert_eq!(parse_notation("<LocalLeader>x", &space)[0], space);
+    assert_eq!(parse("<Leader>a")[0], KeyChord::char('\\'));
+}
+
+#[test]
+fn modifier_chords() {
+    assert_eq!(
+        parse("<C-x>"),
+        vec![KeyChord::new(KeyCode::Char('x'), KeyModifiers::CONTROL)]
+    );
+    assert_eq!(
+        parse("<c-w>v"),
+        vec![
+            KeyChord::new(KeyCode::Char('w'), KeyModifiers::CONTROL),
+            KeyChord::char('v')
+        ]
+    );
+    assert_eq!(
+        parse("<A-j>"),
+        vec![KeyChord::new(KeyCode::Char('j'), KeyModifiers::ALT)]
+    );
+    assert_eq!(parse("<M-j>"), parse("<A-j>"));
+    assert_eq!(
+        parse("<C-S-p>"),
+        vec![KeyChord::new(
+            KeyCode::Char('p'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT
+        )]
+    );
+    assert_eq!(parse("<S-a>"), vec![KeyChord::char('A')]);
+}
+
+#[test]
+fn special_keys() {
+    assert_eq!(
+        parse("<S-Tab>"),
+        vec![KeyChord::new(KeyCode::BackTab, KeyModifiers::NONE)]
+    );
+    assert_eq!(parse("<CR>")[0].code, KeyCode::Enter);
+    assert_eq!(parse("<Enter>")[0].code, KeyCode::Enter);
+    assert_eq!(parse("<Esc>")[0].code, KeyCode::Esc);
+    assert_eq!(parse("<BS>")[0].code, KeyCode::Backspace);
+    assert_eq!(parse("<Space>")[0], KeyChord::char(' '));
+    assert_eq!(parse("<lt>")[0], KeyChord::char('<'));
+    assert_eq!(parse("<Bar>")[0], KeyChord::char('|'));
+    assert_eq!(parse("<F12>")[0].code, KeyCode::F(12));
+    assert_eq!(parse("<Up>")[0].code, KeyCode::Up);
+}
+
+#[test]
+fn unknown_groups_are_typed_literally() {
+    assert_eq!(render(&parse("<foo>")), "<lt>foo>");
+    assert_eq!(parse("<>").len(), 2);
+    assert_eq!(parse("a<").len(), 2);
+}
+
+#[test]
+fn chords_render_back_to_notation() {
+    for lhs in [
+        "gcc",
+        "<C-x>",
+        "<C-w>v",
+        "<A-j>",
+        "<S-Tab>",
+        "<CR>",
+        "<Esc>",
+        "<BS>",
+        "<Space>",
+        "<lt>",
+        "<F5>",
+        "<C-S-p>",
+        "<Up><Down>",
+    ] {
+        assert_eq!(render(&parse(lhs)), lhs);
+    }
+    assert_eq!(render(&parse("<c-x>")), "<C-x>");
+    assert_eq!(render(&parse("<M-j>")), "<A-j>");
+    assert_eq!(render(&parse("<S-a>")), "A");
+    assert_eq!(render(&parse("<Bar>")), "|");
+}
+
+#[test]
+fn leader_specs() {
+    assert_eq!(parse_leader(" "), Some(KeyChord::char(' ')));
+    assert_eq!(parse_leader("<Space>"), Some(KeyChord::char(' ')));
+    assert_eq!(parse_leader(","), Some(KeyChord::char(',')));
+    assert_eq!(parse_leader("\\"), Some(KeyChord::char('\\')));
+    assert_eq!(parse_leader("ab"), None);
+    assert_eq!(parse_leader(""), None);
+}
+
+#[test]
+fn chords_match_key_events() {
+    let ctrl_x = KeyChord::new(KeyCode::Char('x'), KeyModifiers::CONTROL);
+    assert!(ctrl_x.matches(&KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL)));
+    assert!(ctrl_x.matches(&KeyEvent::new(
+        KeyCode::Char('X'),
+        KeyModifiers::CONTROL | KeyModifiers::SHIFT
+    )));
+    assert!(!ctrl_x.matches(&KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE)));
+
+    let back_tab = parse("<S-Tab>").remove(0);
+    assert!(back_tab.matches(&KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)));
+    assert!(back_tab.matches(&KeyEvent::new(KeyCode::Tab, KeyModifiers::SHIFT)));
+    assert!(!back_tab.matches(&KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE)));
+}
+
+fn keymap_app(lhs: &str) -> App {
+    let mut app = app();
+    app.nvim_keymaps = vec![Keymap::new(lhs)];
+    app.mode = AppMode::CustomKeymaps;
+    app.start_exercise();
+    app
+}
+
+#[test]
+fn keymaps_are_typed_chord_by_chord() {
+    let mut app = keymap_app("<C-w>v");
+    press(&mut app, KeyCode::Char('w'));
+    assert_eq!(app.current_key_index, 0);
+    assert_eq!(app.stats.errors, 1);
+
+    app.handle_key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL));
+    assert_eq!(app.current_key_index, 1);
+    press(&mut app, KeyCode::Char('v'));
+    assert_eq!(app.exercises_completed, 1);
+    assert_eq!(app.stats.correct, 2);
+}
+
+#[test]
+fn esc_and_f2_can_be_part_of_a_keymap() {
+    let mut app = keymap_app("jk<Esc>");
+    press(&mut app, KeyCode::Char('j'));
+    press(&mut app, KeyCode::Char('k'));
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.mode, AppMode::CustomKeymaps);
+    assert_eq!(app.exercises_completed, 1);
+
+    let mut app = keymap_app("<F2>x");
+    press(&mut app, KeyCode::F(2));
+    assert_eq!(app.current_key_index, 1);
+    assert_eq!(app.stats.errors, 0);
+}
+
+#[test]
+fn esc_still_leaves_when_not_expected() {
+    let mut app = keymap_app("jk<Esc>");
+    press(&mut app, KeyCode::Char('j'));
+    press(&mut app, KeyCode::Esc);
+    assert_ne!(app.mode, AppMode::CustomKeymaps);
+    // A session left after one key is too short to keep
+    assert!(app.history.records.is_empty());
+}
+
+#[test]
+fn keymap_mode_is_shown_without_a_description() {
+    let mut app = app();
+    let mut keymap = Keymap::new("gx");
+    keymap.mode = Some("x".to_string());
+    app.nvim_keymaps = vec![keymap];
+    app.mode = AppMode::CustomKeymaps;
+    app.start_exercise();
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal.draw(|f| ui::render(f, &app)).unwrap();
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|cell| cell.symbol())
+        .collect();
+    assert!(screen.contains("Mode: x"));
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::{custom, type_str};
+use lazydvorak::layout::KeyboardLayout;
+
+fn layout(id: &str) -> KeyboardLayout {
+    KeyboardLayout::find_builtin(id).expect("built-in layout")
+}
+
+#[test]
+fn remaps_keys_by_physical_position() {
+    let qwerty = layout("qwerty");
+    let dvorak = layout("dvorak");
+    let colemak = layout("colemak");
+
+    // The QWERTY q, s and d keys type ; o e on Dvorak Programmer
+    assert_eq!(dvorak.remap_from(&qwerty, 'q'), Some(';'));
+    assert_eq!(dvorak.remap_from(&qwerty, 's'), Some('o'));
+    assert_eq!(dvorak.remap_from(&qwerty, 'd'), Some('e'));
+    assert_eq!(dvorak.remap_from(&qwerty, 'z'), Some('\''));
+    assert_eq!(colemak.remap_from(&qwerty, 'e'), Some('f'));
+    assert_eq!(colemak.remap_from(&qwerty, 'k'), Some('e'));
+
+    // And back again
+    assert_eq!(qwerty.remap_from(&dvorak, ';'), Some('q'));
+    assert_eq!(qwerty.remap_from(&dvorak, 'o'), Some('s'));
+}
+
+#[test]
+fn remaps_the_shift_layer() {
+    let qwerty = layout("qwerty");
+    let dvorak = layout("dvorak");
+    assert_eq!(dvorak.remap_from(&qwerty, 'Q'), Some(':'));
+    assert_eq!(dvorak.remap_from(&qwerty, 'S'), Some('O'));
+    assert_eq!(dvorak.remap_from(&qwerty, '"'), Some('_'));
+    assert_eq!(qwerty.remap_from(&dvorak, ':'), Some('Q'));
+}
+
+#[test]
+fn unknown_and_named_keys_are_left_alone() {
+    let qwerty = layout("qwerty");
+    let dvorak = layout("dvorak");
+    assert_eq!(dvorak.remap_from(&qwerty, 'ñ'), None);
+    assert_eq!(dvorak.remap_from(&qwerty, '\t'), None);
+    assert_eq!(dvorak.remap_from(&qwerty, ' '), None);
+}
+
+#[test]
+fn remapping_from_the_same_layout_is_the_identity() {
+    let dvorak = layout("dvorak");
+    for c in ";,.pyaoeui'qjkx".chars() {
+        assert_eq!(dvorak.remap_from(&dvorak, c), Some(c));
+    }
+}
+
+#[test]
+fn only_shift_layer_characters_need_shift() {
+    let qwerty = layout("qwerty");
+    assert!(qwerty.needs_shift('A'));
+    assert!(qwerty.needs_shift('!'));
+    assert!(qwerty.needs_shift(':'));
+    assert!(!qwerty.needs_shift('a'));
+    assert!(!qwerty.needs_shift('1'));
+    assert!(!qwerty.needs_shift(';'));
+
+    // Dvorak Programmer puts the digits on the shift layer
+    let dvorak = layout("dvorak");
+    assert!(dvorak.needs_shift('7'));
+    assert!(!dvorak.needs_shift('['));
+    assert!(dvorak.needs_shift(':'));
+    assert!(!dvorak.needs_shift(';'));
+}
+
+#[test]
+fn named_and_unknown_keys_never_need_shift() {
+    let qwerty = layout("qwerty");
+    assert!(!qwerty.needs_shift('\t'));
+    assert!(!qwerty.needs_shift('\n'));
+    assert!(!qwerty.needs_shift('ñ'));
+}
+
+#[test]
+fn emulation_translates_keys_while_practising() {
+    // Typed on a system set to QWERTY, s d q are o e ; on Dvorak Programmer
+    let mut app = custom("oe;x");
+    app.emulation_source = Some(layout("qwerty"));
+    type_str(&mut app, "sdq");
+    assert_eq!(app.typed_text, "oe;");
+    assert_eq!(app.stats.errors, 0);
+}
+
+#[test]
+fn emulation_needs_matching_rows() {
+    let qwerty = layout("qwerty");
+    assert!(layout("dvorak").same_geometry(&qwerty));
+    assert!(layout("colemak-dh").same_geometry(&qwerty));
+
+    let mut short = qwerty.clone();
+    short.rows[1].keys.pop();
+    assert!(!short.same_geometry(&qwerty));
+    short.rows.pop();
+    assert!(!qwerty.same_geometry(&short));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{custom, press, type_str};
+use crossterm::event::KeyCode;
+use lazydvorak::types::{AppMode, MistakePolicy};
+
+#[test]
+fn block_waits_for_the_right_key() {
+    let mut app = custom("ab");
+    type_str(&mut app, "x");
+    assert_eq!(app.typed_text, "");
+    assert_eq!(app.current_key_index, 0);
+    assert_eq!(app.stats.errors, 1);
+    assert_eq!(app.typed_errors.get(&0), Some(&'x'));
+
+    type_str(&mut app, "ab");
+    assert_eq!(app.exercises_completed, 1);
+    assert_eq!(app.stats.correct, 2);
+}
+
+#[test]
+fn sudden_death_ends_only_the_current_exercise() {
+    let mut app = custom("abc");
+    app.mistake_policy = MistakePolicy::SuddenDeath;
+    type_str(&mut app, "ax");
+    assert_eq!(app.mode, AppMode::CustomFile);
+    assert_eq!(app.exercises_completed, 1);
+    assert_eq!(app.exercises_failed, 1);
+    assert_eq!(app.stats.errors, 1);
+    // The next exercise starts from scratch
+    assert_eq!(app.typed_text, "");
+    assert!(app.typed_errors.is_empty());
+
+    type_str(&mut app, "abc");
+    assert_eq!(app.exercises_completed, 2);
+    assert_eq!(app.exercises_failed, 1);
+}
+
+#[test]
+fn free_typing_inserts_wrong_keys_until_backspaced() {
+    let mut app = custom("ab");
+    app.mistake_policy = MistakePolicy::Free;
+    type_str(&mut app, "xb");
+    assert_eq!(app.typed_text, "xb");
+    assert_eq!(app.current_key_index, 2);
+    assert_eq!(app.typed_errors.get(&0), Some(&'x'));
+    assert_eq!(app.exercises_completed, 0);
+
+    press(&mut app, KeyCode::Backspace);
+    assert_eq!(app.typed_errors.get(&0), Some(&'x'));
+    press(&mut app, KeyCode::Backspace);
+    assert!(app.typed_errors.is_empty());
+
+    type_str(&mut app, "ab");
+    assert_eq!(app.exercises_completed, 1);
+    assert_eq!(app.stats.errors, 1);
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use common::{custom, press};
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, ModifierKeyCode,
+};
+use lazydvorak::app::App;
+use lazydvorak::types::ModifierState;
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+fn enhanced(code: KeyCode, kind: KeyEventKind, state: KeyEventState) -> KeyEvent {
+    KeyEvent::new_with_kind_and_state(code, KeyModifiers::NONE, kind, state)
+}
+
+fn enhanced_app(exercise: &str) -> App {
+    let mut app = custom(exercise);
+    app.keyboard_enhanced = true;
+    app
+}
+
+#[test]
+fn shifted_keys_light_the_other_hands_shift() {
+    // Dvorak Programmer: a is under the left pinky, h under the right index
+    let mut app = custom("Ah");
+    app.handle_key(key(KeyCode::Char('A'), KeyModifiers::SHIFT));
+    let state = app.modifier_state();
+    assert!(state.right_shift && !state.left_shift);
+    assert!(app.shift_pressed);
+
+    app.handle_key(key(KeyCode::Char('H'), KeyModifiers::SHIFT));
+    let state = app.modifier_state();
+    assert!(state.left_shift && !state.right_shift);
+
+    press(&mut app, KeyCode::Char('h'));
+    assert_eq!(app.modifier_state(), ModifierState::default());
+    assert!(!app.shift_pressed);
+}
+
+#[test]
+fn shift_layer_characters_light_shift_without_the_modifier() {
+    // Some terminals report ':' without SHIFT
+    let mut app = custom(":");
+    press(&mut app, KeyCode::Char(':'));
+    let state = app.modifier_state();
+    assert!(state.right_shift);
+}
+
+#[test]
+fn ctrl_and_alt_light_both_sides() {
+    let mut app = custom("x");
+    app.handle_key(key(
+        KeyCode::Char('w'),
+        KeyModifiers::CONTROL | KeyModifiers::ALT,
+    ));
+    let state = app.modifier_state();
+    assert!(state.left_ctrl && state.right_ctrl);
+    assert!(state.left_alt && state.right_alt);
+    assert!(!state.left_shift && !state.right_shift);
+    assert!(app.ctrl_pressed && app.alt_pressed);
+}
+
+#[test]
+fn enhanced_reporting_tracks_each_modifier_key() {
+    let mut app = enhanced_app("x");
+    let none = KeyEventState::NONE;
+    app.handle_key(enhanced(
+        KeyCode::Modifier(ModifierKeyCode::LeftShift),
+        KeyEventKind::Press,
+        none,
+    ));
+    app.handle_key(enhanced(
+        KeyCode::Modifier(ModifierKeyCode::RightControl),
+        KeyEventKind::Press,
+        none,
+    ));
+    let state = app.modifier_state();
+    assert!(state.left_shift && !state.right_shift);
+    assert!(state.right_ctrl && !state.left_ctrl);
+    assert!(app.shift_pressed && app.ctrl_pressed);
+
+    app.handle_key(enhanced(
+        KeyCode::Modifier(ModifierKeyCode::LeftShift),
+        KeyEventKind::Release,
+        none,
+    ));
+    let state = app.modifier_state();
+    assert!(!state.left_shift);
+    assert!(state.right_ctrl);
+    assert!(!app.shift_pressed);
+
+    // Modifier presses are not typed
+    assert_eq!(app.current_key_index, 0);
+    assert_eq!(app.stats.errors, 0);
+}
+
+#[test]
+fn key_releases_are_not_typed() {
+    let mut app = enhanced_app("ab");
+    let none = KeyEventState::NONE;
+    app.handle_key(enhanced(KeyCode::Char('a'), KeyEventKind::Press, none));
+    app.handle_key(enhanced(KeyCode::Char('a'), KeyEventKind::Release, none));
+    assert_eq!(app.current_key_index, 1);
+    assert_eq!(app.stats.errors, 0);
+}
+
+#[test]
+fn caps_lock_state_applies_to_letters() {
+    let mut app = enhanced_app("Ab1x");
+    let caps = KeyEventState::CAPS_LOCK;
+    app.handle_key(enhanced(KeyCode::Char('a'), KeyEventKind::Press, caps));
+    assert!(app.caps_lock);
+    assert!(app.modifier_state().caps_lock);
+    assert_eq!(app.current_key_index, 1);
+
+    // Shift with Caps Lock on types lowercase; digits are untouched
+    app.handle_key(KeyEvent::new_with_kind_and_state(
+        KeyCode::Char('B'),
+        KeyModifiers::SHIFT,
+        KeyEventKind::Press,
+        caps,
+    ));
+    app.handle_key(enhanced(KeyCode::Char('1'), KeyEventKind::Press, caps));
+    assert_eq!(app.typed_text, "Ab1");
+    assert_eq!(app.stats.errors, 0);
+
+    app.handle_key(enhanced(
+        KeyCode::Char('x'),
+        KeyEventKind::Press,
+        KeyEventState::NONE,
+    ));
+    assert!(!app.caps_lock);
+}
+
+#[test]
+fn caps_lock_key_toggles_the_indicator() {
+    let mut app = custom("x");
+    press(&mut app, KeyCode::CapsLock);
+    assert!(app.caps_lock);
+    assert_eq!(app.current_key_index, 0);
+    press(&mut app, KeyCode::CapsLock);
+    assert!(!app.caps_lock);
+}
@@ -0,0 +1,169 @@
+use lazydvorak::exercises::get_sentences_japanese;
+use lazydvorak::romaji::{RomajiStep, candidates, feed, hint};
+
+// Feeds `input` against `reading`; Ok with the kana still left and the romaji
+// pending, or Err with the position of the rejected key
+fn type_romaji(reading: &str, input: &str) -> Result<(String, String), usize> {
+    let reading: Vec<char> = reading.chars().collect();
+    let mut done = 0;
+    let mut pending = String::new();
+    for (i, c) in input.chars().enumerate() {
+        match feed(&reading[done..], &pending, c) {
+            RomajiStep::Accepted {
+                converted,
+                pending: next,
+            } => {
+                done += converted;
+                pending = next;
+            }
+            RomajiStep::Rejected { .. } => return Err(i),
+        }
+    }
+    Ok((reading[done..].iter().collect(), pending))
+}
+
+fn completes(reading: &str, input: &str) -> bool {
+    type_romaji(reading, input) == Ok((String::new(), String::new()))
+}
+
+#[test]
+fn alternative_spellings() {
+    assert!(completes("しけん", "shikenn"));
+    assert!(completes("しけん", "sikenn"));
+    assert!(completes("つくえ", "tsukue"));
+    assert!(completes("つくえ", "tukue"));
+    assert!(completes("ちず", "chizu"));
+    assert!(completes("ちず", "tizu"));
+    assert!(completes("ふじ", "fuji"));
+    assert!(completes("ふじ", "huzi"));
+    assert!(completes("しゃしん", "shashinn"));
+    assert!(completes("しゃしん", "syasinn"));
+    assert!(completes("かいしゃ", "kaisha"));
+    assert!(completes("きゅう", "kyuu"));
+}
+
+#[test]
+fn wrong_keys_are_rejected_with_a_hint() {
+    assert_eq!(type_romaji("すし", "sisi"), Err(1));
+    let reading: Vec<char> = "すし".chars().collect();
+    assert_eq!(
+        feed(&reading, "s", 'i'),
+        RomajiStep::Rejected {
+            expected: Some('u')
+        }
+    );
+    assert_eq!(
+        feed(&reading, "", 'k'),
+        RomajiStep::Rejected {
+            expected: Some('s')
+        }
+    );
+}
+
+#[test]
+fn partial_units_stay_pending() {
+    assert_eq!(
+        type_romaji("つき", "ts"),
+        Ok(("つき".to_string(), "ts".to_string()))
+    );
+    assert_eq!(
+        type_romaji("つき", "tsuk"),
+        Ok(("き".to_string(), "k".to_string()))
+    );
+}
+
+#[test]
+fn n_before_consonants_and_at_the_end() {
+    assert!(completes("ほんだ", "honda"));
+    assert!(!completes("ほんだ", "hondda"));
+    assert!(completes("ほんだ", "honnda"));
+    assert!(completes("ほんだ", "hoxnda"));
+
+    // A final ん needs nn, a single n is still waiting for its unit
+    assert_eq!(
+        type_romaji("ほん", "hon"),
+        Ok(("ん".to_string(), "n".to_string()))
+    );
+    assert!(completes("ほん", "honn"));
+}
+
+#[test]
+fn n_before_a_vowel_or_y_needs_nn() {
+    // "kinen" would read きねん, so ん has to be typed nn here
+    assert_eq!(type_romaji("きんえん", "kine"), Err(3));
+    assert!(completes("きんえん", "kinnenn"));
+
+    // "konya" would read こにゃ
+    assert_eq!(type_romaji("こんや", "kony"), Err(3));
+    assert!(completes("こんや", "konnya"));
+
+    // Before な行 a single n would merge into the next kana
+    assert_eq!(type_romaji("こんな", "kona"), Err(3));
+    assert!(completes("こんな", "konnna"));
+}
+
+#[test]
+fn sokuon_doubles_the_next_consonant() {
+    assert!(completes("きって", "kitte"));
+    assert!(completes("きって", "kixtute"));
+    assert!(completes("きって", "kiltsute"));
+    assert!(completes("がっこう", "gakkou"));
+    assert!(completes("まっちゃ", "matcha"));
+    assert!(completes("まっちゃ", "maccha"));
+    assert!(completes("まっちゃ", "mattya"));
+    assert_eq!(type_romaji("きって", "kite"), Err(3));
+}
+
+#[test]
+fn sokuon_hint_prefers_the_doubled_consonant() {
+    let rest: Vec<char> = "って".chars().collect();
+    assert_eq!(hint(&rest, "").as_deref(), Some("t"));
+    assert_eq!(candidates(&rest)[0], ("t".to_string(), 1));
+
+    let rest: Vec<char> = "し".chars().collect();
+    assert_eq!(hint(&rest, "").as_deref(), Some("shi"));
+    assert_eq!(hint(&rest, "si").as_deref(), Some("si"));
+}
+
+#[test]
+fn katakana_and_punctuation() {
+    assert!(completes("コーヒー", "ko-hi-"));
+    assert!(completes("ティー", "thi-"));
+    assert!(completes("ディズニー", "dhizuni-"));
+    assert!(completes("はい、そう。", "hai,sou."));
+    assert!(completes("ABC", "ABC"));
+}
+
+#[test]
+fn every_sentence_can_be_typed_from_hints() {
+    for sentence in get_sentences_japanese() {
+        let reading: Vec<char> = sentence.reading.chars().collect();
+        let mut done = 0;
+        let mut pending = String::new();
+        let mut typed = String::new();
+
+        while done < reading.len() {
+            assert!(typed.len() < reading.len() * 4, "{}", sentence.reading);
+            let suggestion = hint(&reading[done..], &pending).expect("a hint for every unit");
+            let c = suggestion
+                .chars()
+                .nth(pending.chars().count())
+                .expect("the hint continues the pending romaji");
+            typed.push(c);
+            match feed(&reading[done..], &pending, c) {
+                RomajiStep::Accepted {
+                    converted,
+                    pending: next,
+                } => {
+                    done += converted;
+                    pending = next;
+                }
+                RomajiStep::Rejected { .. } => {
+                    panic!("hint {:?} rejected in {}", typed, sentence.reading)
+                }
+            }
+        }
+        assert!(pending.is_empty(), "{}", sentence.reading);
+        assert!(completes(sentence.reading, &typed));
+    }
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::{app, press};
+use crossterm::event::KeyCode;
+use lazydvorak::app::App;
+use lazydvorak::stats::Stats;
+use lazydvorak::types::{AppMode, ExerciseCount};
+use std::time::{Duration, Instant};
+
+fn timed_app(count: ExerciseCount) -> App {
+    let mut app = app();
+    app.exercise_count = count;
+    app.mode = AppMode::WordsSimple;
+    app.start_exercise();
+    app
+}
+
+fn type_next_key(app: &mut App) {
+    let c = app.target_text.chars().nth(app.current_key_index).unwrap();
+    press(app, KeyCode::Char(c));
+}
+
+#[test]
+fn the_clock_starts_on_the_first_key() {
+    let mut app = timed_app(ExerciseCount::Seconds30);
+    assert_eq!(app.time_remaining(), Some(Duration::from_secs(30)));
+    app.on_tick();
+    assert_eq!(app.time_remaining(), Some(Duration::from_secs(30)));
+
+    type_next_key(&mut app);
+    let remaining = app.time_remaining().unwrap();
+    assert!(remaining < Duration::from_secs(30));
+    assert!(remaining > Duration::from_secs(29));
+    assert_eq!(timed_app(ExerciseCount::Count10).time_remaining(), None);
+}
+
+#[test]
+fn wpm_is_measured_over_the_time_limit_only() {
+    let mut app = timed_app(ExerciseCount::Seconds15);
+    type_next_key(&mut app);
+    type_next_key(&mut app);
+    // The tick that notices the end arrives long after the window closed
+    app.stats.start_time = Some(Instant::now() - Duration::from_secs(40));
+    app.on_tick();
+
+    assert_ne!(app.mode, AppMode::WordsSimple);
+    let record = &app.history.records[0];
+    assert_eq!(record.correct, 2);
+    assert_eq!(record.duration_secs, 15.0);
+    assert!((record.wpm - 1.6).abs() < 0.001);
+}
+
+#[test]
+fn keys_after_the_limit_are_dropped() {
+    let mut app = timed_app(ExerciseCount::Seconds15);
+    type_next_key(&mut app);
+    app.stats.start_time = Some(Instant::now() - Duration::from_secs(16));
+    type_next_key(&mut app);
+
+    let record = &app.history.records[0];
+    assert_eq!(record.correct + record.errors, 1);
+    assert_eq!(record.duration_secs, 15.0);
+}
+
+#[test]
+fn a_finished_clock_stays_stopped() {
+    let mut stats = Stats::new();
+    assert_eq!(stats.elapsed(), Duration::ZERO);
+
+    let start = Instant::now() - Duration::from_secs(20);
+    stats.start_time = Some(start);
+    stats.finish(start + Duration::from_secs(10));
+    stats.finish(start + Duration::from_secs(15));
+    assert_eq!(stats.elapsed(), Duration::from_secs(10));
+    assert_eq!(stats.elapsed_secs(), 10.0);
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use common::{custom, press, type_str};
+use crossterm::event::KeyCode;
+use lazydvorak::text::{display_width, grapheme_at, grapheme_count, graphemes, pop_grapheme};
+use lazydvorak::types::MistakePolicy;
+
+#[test]
+fn counts_graphemes_not_bytes_or_chars() {
+    assert_eq!(grapheme_count("canción"), 7);
+    assert_eq!(grapheme_count("日本語"), 3);
+    assert_eq!(grapheme_count("e\u{301}"), 1);
+    assert_eq!(grapheme_count("👍🏽"), 1);
+    assert_eq!(grapheme_count("🇯🇵"), 1);
+    assert_eq!(graphemes("a·b"), vec!["a", "·", "b"]);
+    assert_eq!(grapheme_at("niño", 2), Some("ñ"));
+    assert_eq!(grapheme_at("niño", 4), None);
+}
+
+#[test]
+fn pops_whole_graphemes() {
+    let mut text = "cafe\u{301}".to_string();
+    assert_eq!(pop_grapheme(&mut text).as_deref(), Some("e\u{301}"));
+    assert_eq!(text, "caf");
+
+    let mut text = "東京".to_string();
+    assert_eq!(pop_grapheme(&mut text).as_deref(), Some("京"));
+    assert_eq!(text, "東");
+
+    let mut text = String::new();
+    assert_eq!(pop_grapheme(&mut text), None);
+}
+
+#[test]
+fn measures_terminal_cells() {
+    assert_eq!(display_width("a"), 1);
+    assert_eq!(display_width("é"), 1);
+    assert_eq!(display_width("語"), 2);
+    assert_eq!(display_width("\t"), 1);
+}
+
+#[test]
+fn accented_latin_text_completes() {
+    let mut app = custom("¿Qué pasó, señor?");
+    type_str(&mut app, "¿Qué pas");
+    assert_eq!(app.current_key_index, 8);
+    assert_eq!(app.typed_text, "¿Qué pas");
+
+    type_str(&mut app, "ó, señor?");
+    assert_eq!(app.exercises_completed, 1);
+    assert_eq!(app.stats.correct, 17);
+    assert_eq!(app.stats.errors, 0);
+    assert_eq!(app.stats.total_chars, 17);
+}
+
+#[test]
+fn decomposed_accents_take_one_position() {
+    let mut app = custom("cafe\u{301} ok");
+    type_str(&mut app, "cafe");
+    assert_eq!(app.current_key_index, 3);
+    assert_eq!(app.pending_input, "e");
+
+    type_str(&mut app, "\u{301}");
+    assert_eq!(app.current_key_index, 4);
+    assert_eq!(app.typed_text, "cafe\u{301}");
+
+    type_str(&mut app, " ok");
+    assert_eq!(app.exercises_completed, 1);
+    assert_eq!(app.stats.errors, 0);
+}
+
+#[test]
+fn a_complete_cluster_is_one_correct_keystroke() {
+    let mut app = custom("e\u{301}x");
+    type_str(&mut app, "e\u{301}");
+    let stats = app.stats.key_stats();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].key, 'e');
+    assert_eq!(stats[0].attempts, 1);
+    assert_eq!(stats[0].errors, 0);
+
+    // A wrong key partway through fails the whole cluster
+    let mut app = custom("e\u{301}x");
+    type_str(&mut app, "ex");
+    let stats = app.stats.key_stats();
+    assert_eq!(
+        (stats[0].key, stats[0].attempts, stats[0].errors),
+        ('e', 1, 1)
+    );
+}
+
+#[test]
+fn cjk_text_completes() {
+    let mut app = custom("日本語を話す");
+    type_str(&mut app, "日本語");
+    assert_eq!(app.current_key_index, 3);
+
+    type_str(&mut app, "を話す");
+    assert_eq!(app.exercises_completed, 1);
+    assert_eq!(app.stats.total_chars, 6);
+}
+
+#[test]
+fn emoji_and_middle_dot_complete() {
+    let mut app = custom("a·👍🏽");
+    type_str(&mut app, "a·👍🏽");
+    assert_eq!(app.exercises_completed, 1);
+    assert_eq!(app.stats.correct, 3);
+}
+
+#[test]
+fn wrong_key_is_recorded_at_grapheme_position() {
+    let mut app = custom("señal");
+    type_str(&mut app, "sen");
+    assert_eq!(app.current_key_index, 2);
+    assert_eq!(app.typed_errors.get(&2), Some(&'n'));
+    assert_eq!(app.stats.errors, 1);
+
+    type_str(&mut app, "ñal");
+    assert_eq!(app.exercises_completed, 1);
+}
+
+#[test]
+fn free_typing_backspace_removes_graphemes() {
+    let mut app = custom("日本");
+    app.mistake_policy = MistakePolicy::Free;
+    type_str(&mut app, "日x");
+    assert_eq!(app.current_key_index, 2);
+    assert_eq!(app.exercises_completed, 0);
+
+    press(&mut app, KeyCode::Backspace);
+    assert_eq!(app.current_key_index, 1);
+    assert_eq!(app.typed_text, "日");
+
+    type_str(&mut app, "本");
+    assert_eq!(app.exercises_completed, 1);
+}
+
+#[test]
+fn backspace_discards_a_partial_cluster() {
+    let mut app = custom("e\u{301}");
+    type_str(&mut app, "e");
+    press(&mut app, KeyCode::Backspace);
+    assert!(app.pending_input.is_empty());
+    assert_eq!(app.current_key_index, 0);
+    assert!(app.typed_text.is_empty());
+}
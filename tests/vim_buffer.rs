@@ -0,0 +1,193 @@
+use lazydvorak::keymaps::{KeyChord, parse_notation};
+use lazydvorak::vim_buffer::{VimBuffer, VimMode};
+
+fn feed(buffer: &mut VimBuffer, keys: &str) {
+    for chord in parse_notation(keys, &KeyChord::char('\\')) {
+        buffer.handle_key(chord.code);
+    }
+}
+
+fn cursor_after(text: &str, start: (usize, usize), keys: &str) -> (usize, usize) {
+    let mut buffer = VimBuffer::new(text, start.0, start.1);
+    feed(&mut buffer, keys);
+    (buffer.row, buffer.col)
+}
+
+fn text_after(text: &str, start: (usize, usize), keys: &str) -> String {
+    let mut buffer = VimBuffer::new(text, start.0, start.1);
+    feed(&mut buffer, keys);
+    buffer.text()
+}
+
+const LINE: &str = "let value = compute(a, b);";
+const LINES: &str = "first line\n  second line\n\nfourth line\nlast";
+
+#[test]
+fn character_and_line_motions() {
+    assert_eq!(cursor_after(LINE, (0, 0), "l"), (0, 1));
+    assert_eq!(cursor_after(LINE, (0, 5), "h"), (0, 4));
+    assert_eq!(cursor_after(LINE, (0, 5), "$"), (0, 25));
+    assert_eq!(cursor_after(LINE, (0, 5), "0"), (0, 0));
+    assert_eq!(cursor_after(LINES, (0, 5), "j^"), (1, 2));
+    assert_eq!(cursor_after(LINES, (1, 5), "k"), (0, 5));
+    assert_eq!(cursor_after(LINES, (0, 0), "G"), (4, 0));
+    assert_eq!(cursor_after(LINES, (4, 2), "gg"), (0, 0));
+}
+
+#[test]
+fn motions_stay_inside_the_buffer() {
+    assert_eq!(cursor_after(LINE, (0, 0), "h"), (0, 0));
+    assert_eq!(cursor_after(LINE, (0, 25), "l"), (0, 25));
+    assert_eq!(cursor_after(LINES, (0, 0), "k"), (0, 0));
+    assert_eq!(cursor_after(LINES, (4, 0), "j"), (4, 0));
+    assert_eq!(cursor_after(LINES, (0, 9), "jj"), (2, 0));
+}
+
+#[test]
+fn word_motions() {
+    assert_eq!(cursor_after(LINE, (0, 0), "w"), (0, 4));
+    assert_eq!(cursor_after(LINE, (0, 12), "w"), (0, 19));
+    assert_eq!(cursor_after(LINE, (0, 12), "W"), (0, 23));
+    assert_eq!(cursor_after(LINE, (0, 0), "e"), (0, 2));
+    assert_eq!(cursor_after(LINE, (0, 12), "b"), (0, 10));
+    assert_eq!(cursor_after(LINES, (0, 6), "w"), (1, 2));
+}
+
+#[test]
+fn find_motions_and_repeats() {
+    assert_eq!(cursor_after(LINE, (0, 0), "f("), (0, 19));
+    assert_eq!(cursor_after(LINE, (0, 0), "t("), (0, 18));
+    assert_eq!(cursor_after(LINE, (0, 25), "F="), (0, 10));
+    assert_eq!(cursor_after(LINE, (0, 0), "fe;"), (0, 8));
+    assert_eq!(cursor_after(LINE, (0, 25), "Fe;"), (0, 8));
+    assert_eq!(cursor_after(LINE, (0, 0), "fe;,"), (0, 1));
+    assert_eq!(cursor_after(LINE, (0, 0), "fz"), (0, 0));
+}
+
+#[test]
+fn paragraph_motions() {
+    assert_eq!(cursor_after(LINES, (0, 0), "}"), (2, 0));
+    assert_eq!(cursor_after(LINES, (4, 0), "{"), (2, 0));
+}
+
+#[test]
+fn counts_repeat_motions() {
+    assert_eq!(cursor_after(LINE, (0, 0), "3w"), (0, 12));
+    assert_eq!(cursor_after(LINE, (0, 0), "10l"), (0, 10));
+    assert_eq!(cursor_after(LINES, (0, 0), "3G"), (2, 0));
+    assert_eq!(cursor_after(LINE, (0, 0), "2fe"), (0, 8));
+}
+
+#[test]
+fn delete_and_change_operators() {
+    assert_eq!(text_after(LINE, (0, 0), "dw"), "value = compute(a, b);");
+    assert_eq!(text_after(LINE, (0, 0), "d2w"), "= compute(a, b);");
+    assert_eq!(text_after(LINE, (0, 0), "2dw"), "= compute(a, b);");
+    assert_eq!(text_after(LINE, (0, 12), "D"), "let value = ");
+    assert_eq!(text_after(LINE, (0, 0), "dt="), "= compute(a, b);");
+    assert_eq!(
+        text_after(LINE, (0, 4), "cwtotal<Esc>"),
+        "let total = compute(a, b);"
+    );
+    assert_eq!(text_after(LINE, (0, 0), "3x"), " value = compute(a, b);");
+}
+
+#[test]
+fn linewise_operators() {
+    assert_eq!(
+        text_after(LINES, (0, 0), "dd"),
+        "  second line\n\nfourth line\nlast"
+    );
+    assert_eq!(
+        text_after(LINES, (1, 0), "2dd"),
+        "first line\nfourth line\nlast"
+    );
+    assert_eq!(
+        text_after(LINES, (3, 0), "dj"),
+        "first line\n  second line\n"
+    );
+    assert_eq!(
+        text_after(LINES, (0, 0), "yyjp"),
+        "first line\n  second line\nfirst line\n\nfourth line\nlast"
+    );
+    assert_eq!(text_after("one\ntwo", (0, 0), "ddp"), "two\none");
+}
+
+#[test]
+fn text_objects() {
+    assert_eq!(
+        text_after(LINE, (0, 21), "ci(x<Esc>"),
+        "let value = compute(x);"
+    );
+    assert_eq!(text_after(LINE, (0, 21), "da("), "let value = compute;");
+    assert_eq!(text_after(LINE, (0, 6), "diw"), "let  = compute(a, b);");
+    assert_eq!(text_after(LINE, (0, 6), "daw"), "let = compute(a, b);");
+    assert_eq!(
+        text_after("say \"hello there\" now", (0, 7), "ci\"bye<Esc>"),
+        "say \"bye\" now"
+    );
+}
+
+#[test]
+fn insert_mode_edits() {
+    let mut buffer = VimBuffer::new("world", 0, 0);
+    feed(&mut buffer, "ihello ");
+    assert_eq!(buffer.mode, VimMode::Insert);
+    assert!(!buffer.is_idle());
+    feed(&mut buffer, "<Esc>");
+    assert_eq!(buffer.mode, VimMode::Normal);
+    assert_eq!(buffer.text(), "hello world");
+    assert_eq!((buffer.row, buffer.col), (0, 5));
+
+    assert_eq!(text_after("ab", (0, 0), "A!<Esc>"), "ab!");
+    assert_eq!(text_after("  ab", (0, 3), "I-<Esc>"), "  -ab");
+    assert_eq!(text_after("a\nc", (0, 0), "ob<Esc>"), "a\nb\nc");
+    assert_eq!(text_after("b", (0, 0), "Oa<Esc>"), "a\nb");
+    assert_eq!(text_after("ab", (0, 0), "a<BS><BS>x<Esc>"), "xb");
+}
+
+#[test]
+fn single_key_edits() {
+    assert_eq!(text_after("abc", (0, 0), "rX"), "Xbc");
+    assert_eq!(text_after("abc", (0, 0), "3~"), "ABC");
+    assert_eq!(text_after("a\n  b", (0, 0), "J"), "a b");
+    assert_eq!(text_after("abc", (0, 2), "X"), "ac");
+    assert_eq!(text_after("abc", (0, 1), "sZ<Esc>"), "aZc");
+}
+
+#[test]
+fn undo_restores_text_and_cursor() {
+    let mut buffer = VimBuffer::new(LINES, 0, 3);
+    feed(&mut buffer, "ddx");
+    assert_eq!(buffer.text(), "  econd line\n\nfourth line\nlast");
+
+    feed(&mut buffer, "u");
+    assert_eq!(buffer.text(), "  second line\n\nfourth line\nlast");
+    feed(&mut buffer, "u");
+    assert_eq!(buffer.text(), LINES);
+    assert_eq!((buffer.row, buffer.col), (0, 3));
+
+    // Nothing left to undo
+    feed(&mut buffer, "u");
+    assert_eq!(buffer.text(), LINES);
+}
+
+#[test]
+fn insert_session_undoes_as_one_change() {
+    let mut buffer = VimBuffer::new("x", 0, 0);
+    feed(&mut buffer, "Aabc<Esc>u");
+    assert_eq!(buffer.text(), "x");
+}
+
+#[test]
+fn invalid_and_cancelled_commands_are_dropped() {
+    let mut buffer = VimBuffer::new(LINE, 0, 0);
+    feed(&mut buffer, "d");
+    assert_eq!(buffer.pending, "d");
+    assert!(!buffer.is_idle());
+    feed(&mut buffer, "<Esc>");
+    assert!(buffer.is_idle());
+    feed(&mut buffer, "dQ");
+    assert!(buffer.is_idle());
+    assert_eq!(buffer.text(), LINE);
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::{custom, type_str};
+use lazydvorak::exercises::get_vim_challenges;
+use lazydvorak::types::AppMode;
+
+#[test]
+fn every_challenge_is_solved_in_par() {
+    for challenge in get_vim_challenges() {
+        let mut buffer = challenge.buffer();
+        assert!(
+            !challenge.is_solved(&buffer),
+            "'{}' starts solved",
+            challenge.description
+        );
+
+        let keys = challenge.solution_keys();
+        assert_eq!(keys.len(), challenge.par());
+        for chord in keys {
+            buffer.handle_key(chord.code);
+        }
+        assert!(
+            challenge.is_solved(&buffer),
+            "'{}' is not solved by {:?}: got {:?} at {:?}",
+            challenge.description,
+            challenge.solution,
+            buffer.text(),
+            (buffer.row, buffer.col)
+        );
+    }
+}
+
+#[test]
+fn starting_a_drill_clears_the_previous_exercise() {
+    let mut app = custom("e\u{301}x");
+    type_str(&mut app, "ey");
+
+    app.mode = AppMode::VimMotions;
+    app.start_exercise();
+    assert!(app.typed_text.is_empty());
+    assert!(app.pending_input.is_empty());
+    assert!(app.typed_errors.is_empty());
+    assert_eq!(app.current_key_index, 0);
+}
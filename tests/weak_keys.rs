@@ -0,0 +1,95 @@
+use lazydvorak::exercises::build_weak_keys_exercise;
+use lazydvorak::stats::{KeyStat, KeystrokeRecord, Stats};
+use std::collections::HashMap;
+
+const NEUTRAL: [&str; 5] = ["ab", "cd", "ef", "gh", "ij"];
+
+fn key(key: char, attempts: u32, errors: u32, latency_ms: u64) -> KeyStat {
+    KeyStat {
+        key,
+        attempts,
+        errors,
+        total_latency_ms: latency_ms * attempts as u64,
+        timed_attempts: attempts,
+    }
+}
+
+fn pool(weak: &[&str]) -> Vec<String> {
+    weak.iter()
+        .chain(NEUTRAL.iter())
+        .map(|w| w.to_string())
+        .collect()
+}
+
+// Words drawn over many exercises that are not one of the weak words
+fn neutral_picks(pool: &[String], keys: &[KeyStat], bigrams: &HashMap<String, u32>) -> usize {
+    (0..20)
+        .flat_map(|_| {
+            build_weak_keys_exercise(pool, keys, bigrams)
+                .split(' ')
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .filter(|word| NEUTRAL.contains(&word.as_str()))
+        .count()
+}
+
+#[test]
+fn missed_bigrams_follow_a_correct_key() {
+    let mut stats = Stats::new();
+    for (expected, typed) in [('t', 't'), ('h', 'j'), ('h', 'h'), ('e', 'w'), ('e', 'e')] {
+        stats.keystrokes.push(KeystrokeRecord {
+            expected,
+            typed,
+            latency_ms: None,
+            error: expected != typed,
+        });
+    }
+    let missed = stats.missed_bigrams();
+    assert_eq!(missed.len(), 2);
+    assert_eq!(missed.get("th"), Some(&1));
+    assert_eq!(missed.get("he"), Some(&1));
+
+    // A mistake on the very first key has no bigram
+    let mut stats = Stats::new();
+    stats.record_keystroke('a', 'b');
+    assert!(stats.missed_bigrams().is_empty());
+}
+
+#[test]
+fn exercises_have_six_words_without_immediate_repeats() {
+    let pool = pool(&["kl", "mn"]);
+    for _ in 0..20 {
+        let exercise = build_weak_keys_exercise(&pool, &[], &HashMap::new());
+        let words: Vec<&str> = exercise.split(' ').collect();
+        assert_eq!(words.len(), 6);
+        assert!(words.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+    assert_eq!(build_weak_keys_exercise(&[], &[], &HashMap::new()), "");
+}
+
+#[test]
+fn error_prone_keys_are_drilled() {
+    let pool = pool(&["zz", "zx"]);
+    let keys = [
+        key('z', 4, 3, 100),
+        key('a', 4, 0, 100),
+        key('c', 4, 0, 100),
+    ];
+    assert!(neutral_picks(&pool, &keys, &HashMap::new()) < 12);
+}
+
+#[test]
+fn slow_keys_are_drilled() {
+    let pool = pool(&["zz", "zy"]);
+    let mut keys = vec![key('z', 2, 0, 1000), key('y', 2, 0, 1000)];
+    keys.extend("abcdefghij".chars().map(|c| key(c, 2, 0, 100)));
+    assert!(neutral_picks(&pool, &keys, &HashMap::new()) < 12);
+}
+
+#[test]
+fn missed_bigrams_are_drilled() {
+    let pool = pool(&["th", "he"]);
+    let bigrams = HashMap::from([("th".to_string(), 3), ("he".to_string(), 3)]);
+    assert!(neutral_picks(&pool, &[], &bigrams) < 12);
+}